@@ -0,0 +1,61 @@
+//! Multi-account credential management.
+
+// std
+use std::collections::HashMap;
+// crates.io
+use reqwest::Client;
+// self
+use crate::{auth::Authenticator, prelude::*};
+
+/// Manager holding several authenticators keyed by account label or user ID.
+///
+/// Every [`Api`] handle created by the manager shares the same HTTP client and connection pool.
+#[derive(Clone, Debug, Default)]
+pub struct AccountManager {
+	http: Client,
+	accounts: HashMap<String, Authenticator>,
+}
+impl AccountManager {
+	/// Creates an empty manager with a default HTTP client.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates an empty manager sharing the given HTTP client.
+	pub fn with_http_client(http: Client) -> Self {
+		Self { http, accounts: HashMap::new() }
+	}
+
+	/// Registers an account, returning the authenticator previously registered under the label.
+	pub fn insert<L>(&mut self, label: L, authenticator: Authenticator) -> Option<Authenticator>
+	where
+		L: Into<String>,
+	{
+		self.accounts.insert(label.into(), authenticator)
+	}
+
+	/// Removes an account and returns its authenticator.
+	pub fn remove(&mut self, label: &str) -> Option<Authenticator> {
+		self.accounts.remove(label)
+	}
+
+	/// Returns the authenticator registered under the label.
+	pub fn get(&self, label: &str) -> Option<&Authenticator> {
+		self.accounts.get(label)
+	}
+
+	/// Iterates over the registered account labels.
+	pub fn labels(&self) -> impl Iterator<Item = &str> {
+		self.accounts.keys().map(String::as_str)
+	}
+
+	/// Creates an API handle for the account, sharing the manager's HTTP client.
+	///
+	/// Handles of the same account also share its cached bearer token.
+	pub fn api(&self, label: &str) -> Option<Api> {
+		let authenticator =
+			self.accounts.get(label)?.to_owned().with_http_client(self.http.clone());
+
+		Some(Api::with_authenticator(authenticator))
+	}
+}
@@ -1,48 +1,262 @@
 //! X/Twitter OAuth 2.0 Authenticator
+//!
+//! The OAuth 2.0 flows require the `oauth2` feature and the interactive authorization code flow
+//! additionally the `interactive` feature. Without them only pre-issued bearer tokens are
+//! supported.
+
+#[cfg(all(feature = "interactive", not(target_arch = "wasm32")))] mod callback;
+#[cfg(feature = "encryption")] mod encryption;
+#[cfg(feature = "oauth1")] pub mod oauth1;
+#[cfg(feature = "interactive")] mod prompt;
+mod scope;
+mod secret;
+mod store;
+#[cfg(all(feature = "interactive", not(target_arch = "wasm32")))] pub use callback::*;
+#[cfg(feature = "encryption")] pub use encryption::*;
+#[cfg(feature = "interactive")] pub use prompt::*;
+pub use scope::*;
+pub use secret::*;
+pub use store::*;
 
 // std
-use std::{env, io, sync::Arc};
+use std::{
+	fmt::{Debug, Formatter, Result as FmtResult},
+	sync::{Arc, RwLock as StdRwLock},
+	time::Duration,
+};
 // crates.io
+#[cfg(feature = "oauth2")]
 use oauth2::{
-	AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, EmptyExtraTokenFields,
-	EndpointNotSet, EndpointSet, PkceCodeChallenge, RedirectUrl, RefreshToken,
-	RevocationErrorResponseType, Scope, StandardErrorResponse, StandardRevocableToken,
+	AccessToken, AuthType, AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken,
+	EmptyExtraTokenFields, EndpointNotSet, EndpointSet, HttpClientError, PkceCodeChallenge,
+	PkceCodeVerifier, RedirectUrl, RefreshToken, RequestTokenError, RevocationErrorResponseType,
+	RevocationUrl, Scope, StandardErrorResponse, StandardRevocableToken,
 	StandardTokenIntrospectionResponse, StandardTokenResponse, TokenResponse, TokenUrl,
 	basic::{BasicClient, BasicErrorResponseType, BasicTokenType},
 };
 use reqwest::Client;
 use tokio::sync::RwLock;
 // self
-use crate::prelude::*;
+#[cfg(feature = "oauth2")] use crate::time;
+use crate::{prelude::*, time::unix_now};
 
+#[cfg(feature = "oauth2")]
+type TokenRefreshHook = Arc<dyn Send + Sync + Fn(TokenRefresh) -> BoxFuture<'static, ()>>;
+#[cfg(feature = "oauth2")]
+type OauthRequestError = RequestTokenError<
+	HttpClientError<reqwest::Error>,
+	StandardErrorResponse<BasicErrorResponseType>,
+>;
+#[cfg(feature = "oauth2")]
+type OauthTokenResponse = StandardTokenResponse<EmptyExtraTokenFields, BasicTokenType>;
+#[cfg(feature = "oauth2")]
 type OauthClient = oauth2::Client<
 	StandardErrorResponse<BasicErrorResponseType>,
-	StandardTokenResponse<EmptyExtraTokenFields, BasicTokenType>,
+	OauthTokenResponse,
 	StandardTokenIntrospectionResponse<EmptyExtraTokenFields, BasicTokenType>,
 	StandardRevocableToken,
 	StandardErrorResponse<RevocationErrorResponseType>,
 	EndpointSet,
 	EndpointNotSet,
 	EndpointNotSet,
-	EndpointNotSet,
+	EndpointSet,
 	EndpointSet,
 >;
 
+/// Source of bearer tokens used by [`Api`] to authorize requests.
+///
+/// Implement it to plug in custom auth such as corporate token brokers, secret manager rotation
+/// or test stubs without forking the OAuth implementation.
+pub trait AuthProvider: Send + Sync {
+	/// Returns a bearer token, obtaining one if necessary.
+	fn bearer(&self) -> impl MaybeSend + Future<Output = Result<String>>;
+
+	/// Returns a new bearer token after `stale` has been rejected by the API.
+	fn refresh(&self, stale: &str) -> impl MaybeSend + Future<Output = Result<String>>;
+
+	/// Fails early with [`Error::MissingScope`] if a scope required by an endpoint is unavailable.
+	///
+	/// Providers that don't know their scopes accept everything.
+	fn ensure_scopes(&self, required: &[&str]) -> Result<()> {
+		let _ = required;
+
+		Ok(())
+	}
+}
+
+/// Authentication mode determining how a new bearer token is obtained.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AuthMode {
+	/// User context obtained via refresh token or the interactive authorization code flow.
+	#[default]
+	User,
+	/// App-only context obtained via the client credentials grant.
+	///
+	/// Suitable for read-only endpoints such as search and tweet lookup.
+	AppOnly,
+	/// Pre-issued bearer token used as is, bypassing OAuth entirely.
+	Static,
+}
+
+/// How confidential clients authenticate against the token endpoint.
+///
+/// Public clients always send their client ID as a body parameter.
+#[cfg(feature = "oauth2")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TokenAuthMethod {
+	/// `Authorization: Basic` header carrying `client_id:client_secret`, as expected by X.
+	#[default]
+	Basic,
+	/// `client_id` and `client_secret` sent as form body parameters.
+	RequestBody,
+}
+
+/// Retry policy for transient token endpoint failures, independent of data API retries.
+///
+/// Network errors, unparsable responses such as 5xx error pages and the `server_error` and
+/// `temporarily_unavailable` error codes are considered transient.
+#[cfg(feature = "oauth2")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenRetryPolicy {
+	/// Maximum number of attempts including the first one.
+	pub max_attempts: u32,
+	/// Delay before the first retry, doubled after every further attempt.
+	pub base_delay: Duration,
+	/// Upper bound of the delay between attempts.
+	pub max_delay: Duration,
+}
+#[cfg(feature = "oauth2")]
+impl TokenRetryPolicy {
+	/// Creates a policy which never retries.
+	pub fn none() -> Self {
+		Self { max_attempts: 1, ..Default::default() }
+	}
+
+	fn delay(&self, attempt: u32) -> Duration {
+		self.base_delay.saturating_mul(1 << (attempt - 1).min(16)).min(self.max_delay)
+	}
+}
+#[cfg(feature = "oauth2")]
+impl Default for TokenRetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 3,
+			base_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(8),
+		}
+	}
+}
+
+/// Cached bearer tokens are refreshed this long before they expire.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Bearer access token together with its expiry.
+#[derive(Clone, Debug)]
+pub struct BearerToken {
+	/// The access token sent in the `Authorization` header.
+	pub secret: SecretString,
+	/// Unix timestamp in seconds at which the token expires, if known.
+	pub expires_at: Option<u64>,
+}
+impl BearerToken {
+	#[cfg(feature = "oauth2")]
+	fn from_response(token: &OauthTokenResponse) -> Self {
+		Self {
+			secret: token.access_token().secret().as_str().into(),
+			expires_at: token.expires_in().map(|d| unix_now() + d.as_secs()),
+		}
+	}
+
+	/// Returns whether the token expires within the given margin.
+	///
+	/// Tokens without a known expiry are assumed to stay valid.
+	pub fn expires_within(&self, margin: Duration) -> bool {
+		self.expires_at.is_some_and(|t| unix_now() + margin.as_secs() >= t)
+	}
+}
+
+/// Authorization started by [`Authenticator::authorization_url`] awaiting the code exchange.
+#[cfg(feature = "oauth2")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingAuthorization {
+	/// URL the user must open to authorize the app.
+	pub url: String,
+	/// PKCE verifier required by [`Authenticator::exchange_code`].
+	pub pkce_verifier: SecretString,
+	/// CSRF state the redirect must carry.
+	pub state: String,
+}
+
+/// Refresh token rotation passed to the hook registered with
+/// [`Authenticator::on_token_refresh`].
+#[cfg(feature = "oauth2")]
+#[derive(Clone, Debug)]
+pub struct TokenRefresh {
+	/// Refresh token that was replaced, if any.
+	pub old_refresh_token: Option<SecretString>,
+	/// Newly issued refresh token.
+	pub new_refresh_token: SecretString,
+}
+
 /// OAuth 2.0 authenticator for X/Twitter API with token caching and refresh capabilities.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Authenticator {
 	/// The configured OAuth 2.0 client for X/Twitter authentication.
+	#[cfg(feature = "oauth2")]
 	oauth_client: OauthClient,
-	/// Optional refresh token loaded from environment variables.
-	refresh_token: Option<String>,
+	/// Token endpoint of the client credentials grant used in app-only mode.
+	#[cfg(feature = "oauth2")]
+	app_only_token_url: TokenUrl,
+	/// HTTP client used when acting as an [`AuthProvider`].
+	http: Client,
+	/// Mode used to obtain new bearer tokens.
+	mode: AuthMode,
+	/// Scopes requested during the interactive flow.
+	scopes: Scopes,
+	/// Scopes granted with the latest user token, if known.
+	granted_scopes: Arc<StdRwLock<Option<Scopes>>>,
+	/// Whether a failed refresh may fall back to the interactive flow.
+	#[cfg(feature = "interactive")]
+	interactive: bool,
+	/// Whether the interactive flow launches the system browser with the authorization URL.
+	#[cfg(feature = "interactive")]
+	open_browser: bool,
+	/// Prompt collecting the authorization code during the interactive flow.
+	#[cfg(feature = "interactive")]
+	code_prompt: Arc<dyn CodePrompt>,
+	/// Retry policy for transient token endpoint failures.
+	#[cfg(feature = "oauth2")]
+	token_retry: TokenRetryPolicy,
+	/// Backend used to load and persist refresh and access tokens.
+	token_store: Arc<dyn TokenStore>,
+	/// Optional hook fired whenever a new refresh token is issued.
+	#[cfg(feature = "oauth2")]
+	on_token_refresh: Option<TokenRefreshHook>,
+	/// Refresh token rotations waiting to be passed to the hook once the cache lock is released.
+	#[cfg(feature = "oauth2")]
+	pending_refreshes: Arc<std::sync::Mutex<Vec<TokenRefresh>>>,
 	/// Cached bearer token protected by async read-write lock.
-	bearer_token: Arc<RwLock<Option<String>>>,
+	bearer_token: Arc<RwLock<Option<BearerToken>>>,
 }
 impl Authenticator {
 	/// Creates a new authenticator with client credentials and X/Twitter OAuth endpoints.
+	#[cfg(feature = "oauth2")]
 	pub fn new(id: String, secret: String) -> Self {
-		let oauth_client = BasicClient::new(ClientId::new(id))
-			.set_client_secret(ClientSecret::new(secret))
+		Self::with_credentials(id, Some(secret))
+	}
+
+	/// Creates a new authenticator for a public client, which authenticates with PKCE only.
+	///
+	/// Suitable for native and desktop apps that can't keep a client secret confidential.
+	#[cfg(feature = "oauth2")]
+	pub fn public(id: String) -> Self {
+		Self::with_credentials(id, None)
+	}
+
+	#[cfg(feature = "oauth2")]
+	fn with_credentials(id: String, secret: Option<String>) -> Self {
+		let mut oauth_client = BasicClient::new(ClientId::new(id))
+			.set_auth_type(AuthType::BasicAuth)
 			.set_auth_uri(
 				AuthUrl::new("https://x.com/i/oauth2/authorize".into())
 					.expect("url must be valid; qed"),
@@ -54,112 +268,885 @@ impl Authenticator {
 			.set_redirect_uri(
 				RedirectUrl::new("http://localhost:8080/callback".into())
 					.expect("url must be valid; qed"),
+			)
+			.set_revocation_url(
+				RevocationUrl::new("https://api.x.com/2/oauth2/revoke".into())
+					.expect("url must be valid; qed"),
 			);
 
+		// Public clients must not send a secret, only confidential ones have one.
+		if let Some(secret) = secret {
+			oauth_client = oauth_client.set_client_secret(ClientSecret::new(secret));
+		}
+
+		Self::from_parts(oauth_client)
+	}
+
+	/// Creates an authenticator with default settings.
+	fn from_parts(#[cfg(feature = "oauth2")] oauth_client: OauthClient) -> Self {
 		Self {
+			#[cfg(feature = "oauth2")]
 			oauth_client,
-			refresh_token: env::var("X_REFRESH_TOKEN").ok(),
+			#[cfg(feature = "oauth2")]
+			app_only_token_url: TokenUrl::new("https://api.x.com/oauth2/token".into())
+				.expect("url must be valid; qed"),
+			http: Client::new(),
+			mode: AuthMode::User,
+			scopes: Scopes::default(),
+			granted_scopes: Default::default(),
+			#[cfg(feature = "interactive")]
+			interactive: true,
+			#[cfg(feature = "interactive")]
+			open_browser: false,
+			#[cfg(feature = "interactive")]
+			code_prompt: Arc::new(StdinPrompt),
+			#[cfg(feature = "oauth2")]
+			token_retry: TokenRetryPolicy::default(),
+			token_store: Arc::new(MemoryTokenStore::from_env()),
+			#[cfg(feature = "oauth2")]
+			on_token_refresh: None,
+			#[cfg(feature = "oauth2")]
+			pending_refreshes: Default::default(),
 			bearer_token: Default::default(),
 		}
 	}
 
+	/// Creates an authenticator from tokens obtained elsewhere, e.g. by a separate web backend.
+	///
+	/// The bearer cache is pre-populated so the first API call triggers neither a refresh nor the
+	/// interactive flow.
+	#[cfg(feature = "oauth2")]
+	pub fn from_tokens(
+		id: String,
+		secret: String,
+		access_token: String,
+		refresh_token: Option<String>,
+	) -> Self {
+		let access_token = SecretString::new(access_token);
+		let tokens = Tokens {
+			access_token: Some(access_token.clone()),
+			refresh_token: refresh_token.map(SecretString::new),
+			expires_at: None,
+		};
+
+		Self {
+			token_store: Arc::new(MemoryTokenStore::new(tokens)),
+			bearer_token: Arc::new(RwLock::new(Some(BearerToken {
+				secret: access_token,
+				expires_at: None,
+			}))),
+			..Self::new(id, secret)
+		}
+	}
+
+	/// Creates an authenticator using a pre-issued bearer token as is.
+	///
+	/// No OAuth flow is ever started, a rejected token surfaces as [`Error::Unauthorized`].
+	pub fn from_bearer_token(bearer_token: String) -> Self {
+		#[cfg(feature = "oauth2")]
+		let base = Self::public(String::new());
+		#[cfg(not(feature = "oauth2"))]
+		let base = Self::from_parts();
+
+		Self {
+			mode: AuthMode::Static,
+			token_store: Arc::new(MemoryTokenStore::default()),
+			bearer_token: Arc::new(RwLock::new(Some(BearerToken {
+				secret: bearer_token.into(),
+				expires_at: None,
+			}))),
+			..base
+		}
+	}
+
+	/// Replaces the HTTP client used when acting as an [`AuthProvider`].
+	pub fn with_http_client(mut self, http: Client) -> Self {
+		self.http = http;
+
+		self
+	}
+
+	/// Returns the HTTP client used when acting as an [`AuthProvider`].
+	pub fn http_client(&self) -> &Client {
+		&self.http
+	}
+
+	/// Switches the authenticator to app-only mode using the client credentials grant.
+	#[cfg(feature = "oauth2")]
+	pub fn app_only(mut self) -> Self {
+		self.mode = AuthMode::AppOnly;
+
+		self
+	}
+
+	/// Disables the interactive fallback so a failed refresh returns [`Error::OauthRequired`]
+	/// instead of blocking on user input, as required by unattended server deployments.
+	#[cfg(feature = "interactive")]
+	pub fn non_interactive(mut self) -> Self {
+		self.interactive = false;
+
+		self
+	}
+
+	/// Replaces the redirect URI registered for the app, which defaults to
+	/// `http://localhost:8080/callback`.
+	#[cfg(feature = "oauth2")]
+	pub fn with_redirect_uri<U>(mut self, uri: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		self.oauth_client = self.oauth_client.set_redirect_uri(RedirectUrl::new(uri.into())?);
+
+		Ok(self)
+	}
+
+	/// Replaces the authorization endpoint, e.g. to target a mock OAuth server or internal proxy.
+	#[cfg(feature = "oauth2")]
+	pub fn with_auth_url<U>(mut self, url: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		self.oauth_client = self.oauth_client.set_auth_uri(AuthUrl::new(url.into())?);
+
+		Ok(self)
+	}
+
+	/// Replaces the token endpoint used by the authorization code and refresh token grants.
+	#[cfg(feature = "oauth2")]
+	pub fn with_token_url<U>(mut self, url: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		self.oauth_client = self.oauth_client.set_token_uri(TokenUrl::new(url.into())?);
+
+		Ok(self)
+	}
+
+	/// Replaces the token endpoint used by the client credentials grant in app-only mode.
+	#[cfg(feature = "oauth2")]
+	pub fn with_app_only_token_url<U>(mut self, url: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		self.app_only_token_url = TokenUrl::new(url.into())?;
+
+		Ok(self)
+	}
+
+	/// Replaces the token revocation endpoint.
+	#[cfg(feature = "oauth2")]
+	pub fn with_revocation_url<U>(mut self, url: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		self.oauth_client = self.oauth_client.set_revocation_url(RevocationUrl::new(url.into())?);
+
+		Ok(self)
+	}
+
+	/// Replaces how the client authenticates against the token endpoint, which defaults to
+	/// [`TokenAuthMethod::Basic`].
+	#[cfg(feature = "oauth2")]
+	pub fn with_token_auth_method(mut self, method: TokenAuthMethod) -> Self {
+		self.oauth_client = self.oauth_client.set_auth_type(match method {
+			TokenAuthMethod::Basic => AuthType::BasicAuth,
+			TokenAuthMethod::RequestBody => AuthType::RequestBody,
+		});
+
+		self
+	}
+
+	/// Replaces the scopes requested during the interactive flow.
+	pub fn with_scopes(mut self, scopes: Scopes) -> Self {
+		self.scopes = scopes;
+
+		self
+	}
+
+	/// Returns the scopes requested during the interactive flow.
+	pub fn scopes(&self) -> &Scopes {
+		&self.scopes
+	}
+
+	/// Returns the scopes granted with the latest user token, if one has been obtained yet.
+	pub fn granted_scopes(&self) -> Option<Scopes> {
+		self.granted_scopes.read().expect("lock must not be poisoned; qed").clone()
+	}
+
+	/// Fails early with [`Error::MissingScope`] if a required scope was not granted.
+	///
+	/// Until a token has been obtained the requested scopes are checked instead. Scopes of app-only
+	/// and static tokens are unknown, so the check is skipped in those modes.
+	pub fn ensure_scopes(&self, required: &[&str]) -> Result<()> {
+		match self.mode {
+			AuthMode::User =>
+				match &*self.granted_scopes.read().expect("lock must not be poisoned; qed") {
+					Some(granted) => granted.ensure(required),
+					None => self.scopes.ensure(required),
+				},
+			AuthMode::AppOnly | AuthMode::Static => Ok(()),
+		}
+	}
+
+	/// Launches the system browser with the authorization URL during the interactive flow,
+	/// in addition to handing it to the code prompt.
+	#[cfg(feature = "interactive")]
+	pub fn open_browser(mut self, enabled: bool) -> Self {
+		self.open_browser = enabled;
+
+		self
+	}
+
+	/// Replaces the prompt collecting the authorization code, which defaults to [`StdinPrompt`].
+	#[cfg(feature = "interactive")]
+	pub fn with_code_prompt<P>(mut self, code_prompt: P) -> Self
+	where
+		P: 'static + CodePrompt,
+	{
+		self.code_prompt = Arc::new(code_prompt);
+
+		self
+	}
+
+	/// Enables a local HTTP listener on the redirect URI which captures the authorization code
+	/// automatically instead of reading it from stdin.
+	///
+	/// Shorthand for switching the code prompt between [`CallbackServer`] and [`StdinPrompt`].
+	#[cfg(all(feature = "interactive", not(target_arch = "wasm32")))]
+	pub fn callback_server(self, enabled: bool) -> Self {
+		if enabled {
+			self.with_code_prompt(CallbackServer)
+		} else {
+			self.with_code_prompt(StdinPrompt)
+		}
+	}
+
+	/// Replaces the retry policy for transient token endpoint failures.
+	#[cfg(feature = "oauth2")]
+	pub fn with_token_retry(mut self, token_retry: TokenRetryPolicy) -> Self {
+		self.token_retry = token_retry;
+
+		self
+	}
+
+	/// Replaces the token store, which defaults to a [`MemoryTokenStore`] seeded from
+	/// `X_REFRESH_TOKEN`.
+	pub fn with_token_store<S>(mut self, token_store: S) -> Self
+	where
+		S: 'static + TokenStore,
+	{
+		self.token_store = Arc::new(token_store);
+
+		self
+	}
+
+	/// Registers an async hook fired whenever a new refresh token is issued, so applications can
+	/// persist it to their own storage.
+	///
+	/// The hook runs after the token cache lock is released, so it may call back into the client,
+	/// e.g. to make API requests, without deadlocking or stalling concurrent requests.
+	#[cfg(feature = "oauth2")]
+	pub fn on_token_refresh<F, Fut>(mut self, hook: F) -> Self
+	where
+		F: 'static + Send + Sync + Fn(TokenRefresh) -> Fut,
+		Fut: 'static + MaybeSend + Future<Output = ()>,
+	{
+		self.on_token_refresh = Some(Arc::new(move |refresh| Box::pin(hook(refresh))));
+
+		self
+	}
+
+	/// Returns the mode used to obtain new bearer tokens.
+	pub fn mode(&self) -> AuthMode {
+		self.mode
+	}
+
 	/// Obtains a bearer token by attempting refresh first, then falling back to interactive flow.
-	pub async fn request_bearer(&self, http: &Client) -> Result<String> {
-		// Always try to refresh using refresh token first when program starts.
-		if let Ok(bearer) = self.refresh_bearer_token(http).await {
-			return Ok(bearer);
+	///
+	/// In app-only mode the client credentials are exchanged for an app-only bearer token instead,
+	/// while a static bearer token can't be renewed at all.
+	pub async fn request_bearer(&self, http: &Client) -> Result<BearerToken> {
+		let bearer = self.request_bearer_token(http).await;
+
+		self.notify_token_refresh().await;
+
+		bearer
+	}
+
+	async fn request_bearer_token(&self, http: &Client) -> Result<BearerToken> {
+		match self.mode {
+			#[cfg(feature = "oauth2")]
+			AuthMode::User => self.user_bearer_token(http).await,
+			#[cfg(feature = "oauth2")]
+			AuthMode::AppOnly => self.app_only_bearer_token(http).await,
+			#[cfg(not(feature = "oauth2"))]
+			AuthMode::User | AuthMode::AppOnly => {
+				let _ = http;
+
+				Err(Error::OauthRequired)
+			},
+			AuthMode::Static => Err(Error::Unauthorized { response: None }),
 		}
+	}
+
+	#[cfg(feature = "oauth2")]
+	async fn user_bearer_token(&self, http: &Client) -> Result<BearerToken> {
+		// Always try to refresh using refresh token first when program starts.
+		let e = match self.refresh_user_token(http).await {
+			Ok(bearer) => return Ok(bearer),
+			Err(e) => e,
+		};
 
 		// No refresh token or refresh failed, start interactive flow.
-		self.interactive_flow(http).await
+		#[cfg(feature = "interactive")]
+		if self.interactive {
+			return self.run_interactive_flow(http).await;
+		}
+
+		warn!("⚠️ failed to refresh bearer token: {e}");
+
+		Err(Error::OauthRequired)
 	}
 
 	/// Refreshes the bearer token using the stored refresh token.
-	pub async fn refresh_bearer_token(&self, http: &Client) -> Result<String> {
-		let refresh_token = self
-			.oauth_client
-			.exchange_refresh_token(&RefreshToken::new(
-				self.refresh_token.clone().ok_or(Error::OauthRequired)?,
-			))
-			.request_async(http)
+	#[cfg(feature = "oauth2")]
+	pub async fn refresh_bearer_token(&self, http: &Client) -> Result<BearerToken> {
+		let bearer = self.refresh_user_token(http).await;
+
+		self.notify_token_refresh().await;
+
+		bearer
+	}
+
+	#[cfg(feature = "oauth2")]
+	async fn refresh_user_token(&self, http: &Client) -> Result<BearerToken> {
+		let refresh_token =
+			self.token_store.load()?.and_then(|t| t.refresh_token).ok_or(Error::OauthRequired)?;
+		let oauth_refresh_token = RefreshToken::new(refresh_token.expose().to_owned());
+		let token = self
+			.retry_token_request(|| {
+				self.oauth_client.exchange_refresh_token(&oauth_refresh_token).request_async(http)
+			})
 			.await?;
-		let bearer_token = refresh_token.access_token().secret().to_owned();
+		let bearer_token = self.persist_token(&token, Some(refresh_token))?;
 
-		// Log the new refresh token if available, let user decide where to store it.
-		if let Some(new_refresh_token) = refresh_token.refresh_token() {
-			tracing::info!("🔄 new refresh token available: {}", new_refresh_token.secret());
-			tracing::info!("💡 consider updating your X_REFRESH_TOKEN environment variable");
-		}
+		info!("✅ successfully refreshed bearer token");
+
+		Ok(bearer_token)
+	}
+
+	/// Exchanges the client credentials for an app-only bearer token.
+	#[cfg(feature = "oauth2")]
+	pub async fn app_only_bearer_token(&self, http: &Client) -> Result<BearerToken> {
+		let oauth_client = self.oauth_client.clone().set_token_uri(self.app_only_token_url.clone());
+		let token = self
+			.retry_token_request(|| oauth_client.exchange_client_credentials().request_async(http))
+			.await?;
+		let bearer_token = BearerToken::from_response(&token);
 
-		tracing::info!("✅ successfully refreshed bearer token");
+		info!("✅ successfully obtained app-only bearer token");
 
 		Ok(bearer_token)
 	}
 
+	/// Runs a token endpoint request, retrying transient failures according to the retry policy.
+	#[cfg(feature = "oauth2")]
+	async fn retry_token_request<F, Fut>(&self, mut request: F) -> Result<OauthTokenResponse>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<OauthTokenResponse, OauthRequestError>>,
+	{
+		let mut attempt = 1;
+
+		loop {
+			match request().await {
+				Err(e) if attempt < self.token_retry.max_attempts && is_transient(&e) => {
+					let delay = self.token_retry.delay(attempt);
+
+					warn!("⚠️ token request failed, retrying in {delay:?}: {e}");
+
+					time::sleep(delay).await;
+
+					attempt += 1;
+				},
+				resp => return Ok(resp?),
+			}
+		}
+	}
+
 	/// Performs interactive OAuth flow requiring user to authorize in browser and enter code.
-	pub async fn interactive_flow(&self, http: &Client) -> Result<String> {
+	#[cfg(feature = "interactive")]
+	pub async fn interactive_flow(&self, http: &Client) -> Result<BearerToken> {
+		let bearer = self.run_interactive_flow(http).await;
+
+		self.notify_token_refresh().await;
+
+		bearer
+	}
+
+	#[cfg(feature = "interactive")]
+	async fn run_interactive_flow(&self, http: &Client) -> Result<BearerToken> {
+		let authorization = self.authorization_url();
+
+		info!("=== oauth 2.0 authorization ===");
+
+		// The URL is still handed to the prompt, so failing to launch a browser isn't fatal.
+		if self.open_browser
+			&& let Err(e) = prompt::open_browser(&authorization.url)
+		{
+			warn!("⚠️ failed to open browser: {e}");
+		}
+
+		let code = self.code_prompt.prompt(&authorization.url).await?;
+
+		self.exchange_authorization_code(http, &code, authorization.pkce_verifier).await
+	}
+
+	/// Generates the authorization URL together with the PKCE verifier and CSRF state.
+	///
+	/// The returned authorization is serializable, so it can be kept by a web app between
+	/// rendering the URL and handling the redirect in [`Authenticator::exchange_code`].
+	#[cfg(feature = "oauth2")]
+	pub fn authorization_url(&self) -> PendingAuthorization {
 		let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
-		let (auth_url, _csrf) = self
+		let (auth_url, csrf) = self
 			.oauth_client
 			.authorize_url(CsrfToken::new_random)
-			.add_scope(Scope::new("tweet.read".into()))
-			.add_scope(Scope::new("tweet.write".into()))
-			.add_scope(Scope::new("users.read".into()))
-			.add_scope(Scope::new("offline.access".into()))
+			.add_scopes(self.scopes.iter().map(|s| Scope::new(s.to_owned())))
 			.set_pkce_challenge(pkce_challenge)
 			.url();
 
-		tracing::info!("=== oauth 2.0 authorization ===");
-		tracing::info!("open this url in your browser and paste the returned code: {auth_url}");
+		PendingAuthorization {
+			url: auth_url.into(),
+			pkce_verifier: pkce_verifier.into_secret().into(),
+			state: csrf.into_secret(),
+		}
+	}
+
+	/// Exchanges an authorization code for a bearer token, persisting and caching the result.
+	///
+	/// The caller is responsible for checking the redirect's `state` against
+	/// [`PendingAuthorization::state`].
+	#[cfg(feature = "oauth2")]
+	pub async fn exchange_code(
+		&self,
+		http: &Client,
+		code: &str,
+		pkce_verifier: SecretString,
+	) -> Result<BearerToken> {
+		let bearer_token = {
+			let mut cached = self.bearer_token.write().await;
+			let bearer_token = self.exchange_authorization_code(http, code, pkce_verifier).await;
+
+			if let Ok(bearer_token) = &bearer_token {
+				*cached = Some(bearer_token.clone());
+			}
+
+			bearer_token
+		};
 
-		let mut code = String::new();
+		self.notify_token_refresh().await;
 
-		io::stdin().read_line(&mut code)?;
+		bearer_token
+	}
 
+	#[cfg(feature = "oauth2")]
+	async fn exchange_authorization_code(
+		&self,
+		http: &Client,
+		code: &str,
+		pkce_verifier: SecretString,
+	) -> Result<BearerToken> {
 		let code = code.trim();
 
 		if code.is_empty() {
 			Err(Error::any("authorization code cannot be empty"))?;
 		}
 
-		let refresh_token = self
+		let token = self
 			.oauth_client
 			.exchange_code(AuthorizationCode::new(code.to_owned()))
-			.set_pkce_verifier(pkce_verifier)
+			.set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier.into_inner()))
 			.request_async(http)
 			.await?;
-		let bearer_token = refresh_token.access_token().secret().to_owned();
+		let previous = self.token_store.load()?.and_then(|t| t.refresh_token);
+		let bearer_token = self.persist_token(&token, previous)?;
 
-		tracing::info!("✅ successfully obtained bearer token");
+		info!("✅ successfully obtained bearer token");
 
-		if let Some(refresh_token) = refresh_token.refresh_token() {
-			tracing::info!("🔑 refresh token: {}", refresh_token.secret());
-			tracing::info!(
-				"💡 save this refresh token to your X_REFRESH_TOKEN environment variable for future use"
-			);
+		Ok(bearer_token)
+	}
+
+	/// Persists a user token response, records its granted scopes and queues a new refresh token
+	/// for the refresh hook.
+	#[cfg(feature = "oauth2")]
+	fn persist_token(
+		&self,
+		token: &OauthTokenResponse,
+		previous: Option<SecretString>,
+	) -> Result<BearerToken> {
+		let bearer_token = BearerToken::from_response(token);
+		let refresh_token = token.refresh_token().map(|t| SecretString::new(t.secret().as_str()));
+		// An omitted scope means the requested scopes were granted, see RFC 6749 section 5.1.
+		let granted_scopes = token.scopes().map_or_else(
+			|| self.scopes.clone(),
+			|scopes| scopes.iter().map(|s| s.as_str()).collect(),
+		);
+
+		*self.granted_scopes.write().expect("lock must not be poisoned; qed") =
+			Some(granted_scopes);
+
+		// X rotates refresh tokens, persist the new one so the next refresh doesn't fail.
+		self.token_store.save(&Tokens {
+			access_token: Some(bearer_token.secret.clone()),
+			refresh_token: refresh_token.clone().or_else(|| previous.clone()),
+			expires_at: bearer_token.expires_at,
+		})?;
+
+		if let (Some(_), Some(new_refresh_token)) = (&self.on_token_refresh, refresh_token)
+			&& previous.as_ref() != Some(&new_refresh_token)
+		{
+			self.pending_refreshes
+				.lock()
+				.expect("lock must not be poisoned; qed")
+				.push(TokenRefresh { old_refresh_token: previous, new_refresh_token });
 		}
 
 		Ok(bearer_token)
 	}
 
+	/// Passes the queued refresh token rotations to the refresh hook.
+	///
+	/// Must be called without holding the cache lock, as the hook may call back into the client.
+	async fn notify_token_refresh(&self) {
+		#[cfg(feature = "oauth2")]
+		if let Some(hook) = &self.on_token_refresh {
+			let refreshes = std::mem::take(
+				&mut *self.pending_refreshes.lock().expect("lock must not be poisoned; qed"),
+			);
+
+			for refresh in refreshes {
+				hook(refresh).await;
+			}
+		}
+	}
+
+	/// Revokes the current refresh and access tokens and clears them from the cache and store.
+	///
+	/// The tokens are cleared even if X fails to revoke one of them, the first failure is returned
+	/// afterwards.
+	#[cfg(feature = "oauth2")]
+	pub async fn revoke(&self, http: &Client) -> Result<()> {
+		let mut cached = self.bearer_token.write().await;
+		let stored = self.token_store.load()?.unwrap_or_default();
+		let access_token = cached.as_ref().map(|b| b.secret.clone()).or(stored.access_token);
+		// Revoke the refresh token first, so a failure never leaves it able to mint new access
+		// tokens.
+		let refresh_revoked = match stored.refresh_token {
+			Some(refresh_token) =>
+				self.revoke_token(
+					http,
+					StandardRevocableToken::RefreshToken(RefreshToken::new(
+						refresh_token.into_inner(),
+					)),
+				)
+				.await,
+			None => Ok(()),
+		};
+		let access_revoked = match access_token {
+			Some(access_token) =>
+				self.revoke_token(
+					http,
+					StandardRevocableToken::AccessToken(AccessToken::new(
+						access_token.into_inner(),
+					)),
+				)
+				.await,
+			None => Ok(()),
+		};
+
+		*cached = None;
+		*self.granted_scopes.write().expect("lock must not be poisoned; qed") = None;
+
+		let cleared = self.token_store.save(&Tokens::default());
+
+		refresh_revoked.and(access_revoked).and(cleared)?;
+
+		info!("🗑️ successfully revoked tokens");
+
+		Ok(())
+	}
+
+	#[cfg(feature = "oauth2")]
+	async fn revoke_token(&self, http: &Client, token: StandardRevocableToken) -> Result<()> {
+		self.oauth_client.revoke_token(token)?.request_async(http).await?;
+
+		Ok(())
+	}
+
+	/// Injects a newly provisioned refresh token, e.g. after an admin re-authorized the app,
+	/// without restarting the process.
+	///
+	/// The cached bearer token is dropped, so the next request refreshes with the new token.
+	pub async fn set_refresh_token(&self, refresh_token: SecretString) -> Result<()> {
+		// Hold the cache lock to not race with an in-flight refresh.
+		let mut cached = self.bearer_token.write().await;
+
+		self.token_store
+			.save(&Tokens { refresh_token: Some(refresh_token), ..Default::default() })?;
+
+		*cached = None;
+
+		Ok(())
+	}
+
+	/// Removes the refresh token from the store and returns it.
+	///
+	/// The cached bearer token stays usable until it expires.
+	pub async fn take_refresh_token(&self) -> Result<Option<SecretString>> {
+		// Hold the cache lock to not race with an in-flight refresh.
+		let _cached = self.bearer_token.write().await;
+		let mut tokens = self.token_store.load()?.unwrap_or_default();
+		let refresh_token = tokens.refresh_token.take();
+
+		self.token_store.save(&tokens)?;
+
+		Ok(refresh_token)
+	}
+
 	/// Returns cached bearer token or triggers authentication flow if none exists.
+	///
+	/// Tokens about to expire are refreshed proactively to avoid a guaranteed 401 round-trip.
 	pub async fn authenticate(&self, http: &Client) -> Result<String> {
 		// Check if we have a cached token first.
-		if let Some(bearer) = &*self.bearer_token.read().await {
-			return Ok(bearer.to_owned());
+		let stale = match &*self.bearer_token.read().await {
+			Some(bearer) if !bearer.expires_within(REFRESH_MARGIN) =>
+				return Ok(bearer.secret.expose().to_owned()),
+			Some(bearer) => Some(bearer.secret.expose().to_owned()),
+			None => None,
+		};
+
+		// Reuse a persisted access token, a stale one is replaced on the first 401. The store only
+		// holds user tokens, so app-only and static modes must not pick them up.
+		if self.mode == AuthMode::User
+			&& let Some(Tokens { access_token: Some(secret), expires_at, .. }) =
+				self.token_store.load()?
+		{
+			let bearer = BearerToken { secret, expires_at };
+
+			if !bearer.expires_within(REFRESH_MARGIN) {
+				*self.bearer_token.write().await = Some(bearer.clone());
+
+				return Ok(bearer.secret.into_inner());
+			}
 		}
 
-		self.refresh_and_cache(http).await
+		self.refresh_if_stale(http, stale.as_deref()).await
 	}
 
 	/// Refreshes and caches a new bearer token with write lock protection.
 	pub async fn refresh_and_cache(&self, http: &Client) -> Result<String> {
-		// Acquire write lock to prevent multiple simultaneous token requests.
-		let mut cached = self.bearer_token.write().await;
-		let bearer = self.request_bearer(http).await?;
+		let secret = {
+			// Acquire write lock to prevent multiple simultaneous token requests.
+			let mut cached = self.bearer_token.write().await;
+
+			self.request_and_cache(http, &mut cached).await
+		};
+
+		self.notify_token_refresh().await;
+
+		secret
+	}
+
+	/// Refreshes the bearer token after `stale` was rejected, with single-flight semantics.
+	///
+	/// Concurrent callers waiting on the same stale token reuse the result of the first refresh
+	/// instead of hammering the token endpoint and invalidating each other's rotated refresh token.
+	pub async fn refresh_if_stale(&self, http: &Client, stale: Option<&str>) -> Result<String> {
+		let secret = {
+			let mut cached = self.bearer_token.write().await;
+
+			// Another caller refreshed while this one was waiting for the lock, reuse its result.
+			if let Some(bearer) = &*cached
+				&& stale != Some(bearer.secret.expose())
+				&& !bearer.expires_within(REFRESH_MARGIN)
+			{
+				return Ok(bearer.secret.expose().to_owned());
+			}
+
+			self.request_and_cache(http, &mut cached).await
+		};
+
+		self.notify_token_refresh().await;
+
+		secret
+	}
+
+	async fn request_and_cache(
+		&self,
+		http: &Client,
+		cached: &mut Option<BearerToken>,
+	) -> Result<String> {
+		let bearer = self.request_bearer_token(http).await?;
+		let secret = bearer.secret.expose().to_owned();
+
+		*cached = Some(bearer);
+
+		Ok(secret)
+	}
+}
+impl AuthProvider for Authenticator {
+	async fn bearer(&self) -> Result<String> {
+		self.authenticate(&self.http).await
+	}
+
+	async fn refresh(&self, stale: &str) -> Result<String> {
+		self.refresh_if_stale(&self.http, Some(stale)).await
+	}
+
+	fn ensure_scopes(&self, required: &[&str]) -> Result<()> {
+		Authenticator::ensure_scopes(self, required)
+	}
+}
+impl Debug for Authenticator {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		let mut f = f.debug_struct("Authenticator");
+
+		#[cfg(feature = "oauth2")]
+		f.field("oauth_client", &self.oauth_client);
+		f.field("mode", &self.mode)
+			.field("scopes", &self.scopes)
+			.field("granted_scopes", &self.granted_scopes);
+		#[cfg(feature = "interactive")]
+		f.field("interactive", &self.interactive).field("open_browser", &self.open_browser);
+		#[cfg(feature = "oauth2")]
+		f.field("token_retry", &self.token_retry);
+		f.field("token_store", &self.token_store)
+			.field("bearer_token", &self.bearer_token)
+			.finish_non_exhaustive()
+	}
+}
+
+#[cfg(feature = "oauth2")]
+fn is_transient(e: &OauthRequestError) -> bool {
+	match e {
+		RequestTokenError::Request(_) | RequestTokenError::Parse(..) => true,
+		RequestTokenError::ServerResponse(resp) => matches!(
+			resp.error(),
+			BasicErrorResponseType::Extension(code)
+				if code == "server_error" || code == "temporarily_unavailable"
+		),
+		RequestTokenError::Other(_) => false,
+	}
+}
+
+#[cfg(all(test, feature = "oauth2", not(target_arch = "wasm32")))]
+mod tests {
+	// std
+	use std::sync::Mutex;
+	// crates.io
+	use tokio::{
+		io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+		net::TcpListener,
+	};
+	// self
+	use super::*;
+
+	/// Serves one JSON response per connection and returns the received request bodies.
+	async fn serve(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let bodies = Arc::new(Mutex::new(Vec::new()));
+		let received = bodies.clone();
+
+		tokio::spawn(async move {
+			for response in responses {
+				let (mut stream, _) = listener.accept().await.unwrap();
+				let mut reader = BufReader::new(&mut stream);
+				let mut len = 0;
+				let mut line = String::new();
+
+				while reader.read_line(&mut line).await.unwrap() > 2 {
+					if let Some((name, value)) = line.split_once(':')
+						&& name.eq_ignore_ascii_case("content-length")
+					{
+						len = value.trim().parse().unwrap();
+					}
+
+					line.clear();
+				}
+
+				let mut body = vec![0; len];
+
+				reader.read_exact(&mut body).await.unwrap();
+				received.lock().unwrap().push(String::from_utf8(body).unwrap());
+				stream
+					.write_all(
+						format!(
+							"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+							response.len()
+						)
+						.as_bytes(),
+					)
+					.await
+					.unwrap();
+			}
+		});
+
+		(url, bodies)
+	}
+
+	fn user_tokens() -> MemoryTokenStore {
+		MemoryTokenStore::new(Tokens {
+			access_token: Some(SecretString::new("user")),
+			refresh_token: Some(SecretString::new("refresh")),
+			expires_at: None,
+		})
+	}
+
+	#[tokio::test]
+	async fn authenticate_ignores_user_store_in_app_only_mode() {
+		let (url, bodies) = serve(vec![r#"{"access_token":"app","token_type":"bearer"}"#]).await;
+		let authenticator = Authenticator::new("id".into(), "secret".into())
+			.app_only()
+			.with_app_only_token_url(format!("{url}/oauth2/token"))
+			.unwrap()
+			.with_token_store(user_tokens());
+
+		assert_eq!(authenticator.authenticate(&Client::new()).await.unwrap(), "app");
+		assert!(bodies.lock().unwrap()[0].contains("grant_type=client_credentials"));
+
+		let stored = authenticator.token_store.load().unwrap().unwrap();
+
+		assert_eq!(stored.access_token.unwrap().expose(), "user");
+		assert_eq!(stored.refresh_token.unwrap().expose(), "refresh");
+	}
+
+	#[tokio::test]
+	async fn authenticate_reuses_user_store_in_user_mode() {
+		let authenticator =
+			Authenticator::new("id".into(), "secret".into()).with_token_store(user_tokens());
+
+		assert_eq!(authenticator.authenticate(&Client::new()).await.unwrap(), "user");
+	}
+
+	#[tokio::test]
+	async fn revoke_clears_tokens_on_failure() {
+		let authenticator = Authenticator::from_tokens(
+			"id".into(),
+			"secret".into(),
+			"user".into(),
+			Some("refresh".into()),
+		)
+		// Plain HTTP is rejected before any request is sent, failing both revocations.
+		.with_revocation_url("http://localhost/oauth2/revoke")
+		.unwrap();
 
-		*cached = Some(bearer.clone());
+		*authenticator.granted_scopes.write().unwrap() = Some(authenticator.scopes.clone());
 
-		Ok(bearer)
+		assert!(authenticator.revoke(&Client::new()).await.is_err());
+		assert_eq!(authenticator.token_store.load().unwrap(), Some(Tokens::default()));
+		assert!(authenticator.bearer_token.read().await.is_none());
+		assert!(authenticator.granted_scopes().is_none());
 	}
 }
@@ -0,0 +1,153 @@
+//! Local HTTP listener capturing the OAuth 2.0 authorization redirect.
+
+// std
+use std::time::Duration;
+// crates.io
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::{TcpListener, TcpStream},
+	time,
+};
+use url::Url;
+// self
+use crate::{auth::CodePrompt, prelude::*};
+
+/// Longest wait for the authorization redirect before giving up.
+const REDIRECT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Longest wait for a connection to send its request head, e.g. for browser preconnects which
+/// never send one.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prompt running a local HTTP listener on the redirect URI which captures the authorization code
+/// automatically.
+///
+/// The redirect URI and CSRF state are taken from the authorization URL. Unrelated requests, e.g.
+/// from stale tabs, are answered and ignored, and the flow fails if no redirect arrives within 5
+/// minutes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CallbackServer;
+impl CodePrompt for CallbackServer {
+	fn prompt<'a>(&'a self, auth_url: &'a str) -> BoxFuture<'a, Result<String>> {
+		Box::pin(async move {
+			let auth_url = Url::parse(auth_url)?;
+			let param =
+				|name| auth_url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+			let redirect_url = Url::parse(
+				&param("redirect_uri")
+					.ok_or_else(|| Error::any("authorization url must have a redirect uri"))?,
+			)?;
+			let state =
+				param("state").ok_or_else(|| Error::any("authorization url must have a state"))?;
+
+			info!("open this url in your browser to authorize: {auth_url}");
+
+			receive_code(&redirect_url, &state).await
+		})
+	}
+}
+
+/// Listens on the redirect URI until the authorization redirect arrives and returns its code.
+///
+/// Requests whose `state` parameter doesn't match the expected CSRF state are ignored.
+async fn receive_code(redirect_url: &Url, state: &str) -> Result<String> {
+	let host =
+		redirect_url.host_str().ok_or_else(|| Error::any("redirect uri must have a host"))?;
+	let port = redirect_url
+		.port_or_known_default()
+		.ok_or_else(|| Error::any("redirect uri must have a port"))?;
+	let listener = TcpListener::bind((host, port)).await?;
+
+	info!("👂 waiting for authorization redirect on {redirect_url}");
+
+	time::timeout(REDIRECT_TIMEOUT, accept_code(&listener, redirect_url, state))
+		.await
+		.map_err(|_| Error::any("timed out waiting for authorization redirect"))?
+}
+
+/// Answers connections one at a time until one carries the authorization redirect.
+async fn accept_code(listener: &TcpListener, redirect_url: &Url, state: &str) -> Result<String> {
+	loop {
+		let (mut stream, _) = listener.accept().await?;
+		let target = match time::timeout(REQUEST_TIMEOUT, read_request_target(&mut stream)).await {
+			Ok(Ok(target)) => target,
+			// Drop idle or broken connections, e.g. browser preconnects, so the redirect gets
+			// through.
+			_ => continue,
+		};
+		// Stray requests must not abort the flow, so failures answering them are ignored.
+		let Some(url) = target.and_then(|target| redirect_url.join(&target).ok()) else {
+			let _ = respond(&mut stream, "400 Bad Request", "malformed request").await;
+
+			continue;
+		};
+
+		// Browsers also request things like `/favicon.ico`, ignore everything but the callback.
+		if url.path() != redirect_url.path() {
+			let _ = respond(&mut stream, "404 Not Found", "not found").await;
+
+			continue;
+		}
+
+		let param = |name| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+
+		// Redirects of stale tabs or other flows carry a different state, keep waiting for ours.
+		if param("state").as_deref() != Some(state) {
+			let _ = respond(&mut stream, "400 Bad Request", "state mismatch").await;
+
+			continue;
+		}
+		if let Some(error) = param("error") {
+			respond(&mut stream, "400 Bad Request", "authorization failed").await?;
+
+			Err(Error::any(format!("authorization failed: {error}")))?;
+		}
+
+		let Some(code) = param("code") else {
+			let _ = respond(&mut stream, "400 Bad Request", "missing authorization code").await;
+
+			continue;
+		};
+
+		respond(&mut stream, "200 OK", "authorization complete, you may close this window").await?;
+
+		return Ok(code);
+	}
+}
+
+/// Reads the request head and returns the request target of a `GET` request.
+async fn read_request_target(stream: &mut TcpStream) -> Result<Option<String>> {
+	let mut buf = Vec::with_capacity(1024);
+	let mut chunk = [0; 1024];
+
+	// The request line is all we need, stop at the end of the head or after 8 KiB.
+	while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < 8192 {
+		let n = stream.read(&mut chunk).await?;
+
+		if n == 0 {
+			break;
+		}
+
+		buf.extend_from_slice(&chunk[..n]);
+	}
+
+	let head = String::from_utf8_lossy(&buf);
+	let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+
+	match (parts.next(), parts.next()) {
+		(Some("GET"), Some(target)) => Ok(Some(target.to_owned())),
+		_ => Ok(None),
+	}
+}
+
+/// Writes a minimal plain text HTTP response and closes the connection.
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+	let resp = format!(
+		"HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+		body.len()
+	);
+
+	stream.write_all(resp.as_bytes()).await?;
+	stream.shutdown().await?;
+
+	Ok(())
+}
@@ -0,0 +1,134 @@
+//! Encryption layer for token stores.
+
+// std
+use std::{
+	env,
+	fmt::{Debug, Formatter, Result as FmtResult},
+};
+// crates.io
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chacha20poly1305::{
+	AeadCore, ChaCha20Poly1305, KeyInit,
+	aead::{Aead, OsRng},
+};
+// self
+use crate::{
+	auth::{SecretString, TokenStore, Tokens},
+	prelude::*,
+};
+
+/// Token store wrapper encrypting tokens with ChaCha20-Poly1305 before they reach the inner store,
+/// so refresh tokens are never written to disk in plaintext.
+#[derive(Clone)]
+pub struct EncryptedTokenStore<S> {
+	inner: S,
+	cipher: ChaCha20Poly1305,
+}
+impl<S> EncryptedTokenStore<S> {
+	/// Wraps the store using the given 256-bit key.
+	pub fn new(inner: S, key: [u8; 32]) -> Self {
+		Self { inner, cipher: ChaCha20Poly1305::new(&key.into()) }
+	}
+
+	/// Wraps the store using the base64 encoded 256-bit key from `X_TOKEN_KEY`.
+	pub fn from_env(inner: S) -> Result<Self> {
+		let key = env::var("X_TOKEN_KEY").map_err(|_| Error::any("X_TOKEN_KEY not set"))?;
+
+		Ok(Self::new(inner, decode_key(&key)?))
+	}
+
+	/// Wraps the store using a key kept in the platform secret store, generating and saving a new
+	/// one on first use.
+	#[cfg(feature = "keyring")]
+	pub fn from_keyring(inner: S, account: &str) -> Result<Self> {
+		let entry = keyring::Entry::new("xv2api-token-key", account)?;
+		let key = match entry.get_password() {
+			Ok(key) => decode_key(&key)?,
+			Err(keyring::Error::NoEntry) => {
+				let key = Self::generate_key();
+
+				entry.set_password(&BASE64.encode(key))?;
+
+				key
+			},
+			Err(e) => Err(e)?,
+		};
+
+		Ok(Self::new(inner, key))
+	}
+
+	/// Generates a random 256-bit key.
+	pub fn generate_key() -> [u8; 32] {
+		ChaCha20Poly1305::generate_key(&mut OsRng).into()
+	}
+
+	fn encrypt(&self, plaintext: &SecretString) -> Result<SecretString> {
+		let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+		let ciphertext = self
+			.cipher
+			.encrypt(&nonce, plaintext.expose().as_bytes())
+			.map_err(|_| Error::any("failed to encrypt token"))?;
+
+		Ok(BASE64.encode([nonce.as_slice(), &ciphertext].concat()).into())
+	}
+
+	fn decrypt(&self, encoded: &SecretString) -> Result<SecretString> {
+		let bytes = BASE64
+			.decode(encoded.expose())
+			.map_err(|e| Error::any(format!("invalid token: {e}")))?;
+
+		if bytes.len() < 12 {
+			Err(Error::any("invalid token: ciphertext too short"))?;
+		}
+
+		let (nonce, ciphertext) = bytes.split_at(12);
+		let plaintext = self
+			.cipher
+			.decrypt(nonce.into(), ciphertext)
+			.map_err(|_| Error::any("failed to decrypt token"))?;
+
+		Ok(String::from_utf8(plaintext)
+			.map_err(|e| Error::any(format!("invalid token: {e}")))?
+			.into())
+	}
+}
+impl<S> Debug for EncryptedTokenStore<S>
+where
+	S: Debug,
+{
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		f.debug_struct("EncryptedTokenStore").field("inner", &self.inner).finish_non_exhaustive()
+	}
+}
+impl<S> TokenStore for EncryptedTokenStore<S>
+where
+	S: TokenStore,
+{
+	fn load(&self) -> Result<Option<Tokens>> {
+		let Some(tokens) = self.inner.load()? else {
+			return Ok(None);
+		};
+
+		Ok(Some(Tokens {
+			access_token: tokens.access_token.map(|t| self.decrypt(&t)).transpose()?,
+			refresh_token: tokens.refresh_token.map(|t| self.decrypt(&t)).transpose()?,
+			expires_at: tokens.expires_at,
+		}))
+	}
+
+	fn save(&self, tokens: &Tokens) -> Result<()> {
+		self.inner.save(&Tokens {
+			access_token: tokens.access_token.as_ref().map(|t| self.encrypt(t)).transpose()?,
+			refresh_token: tokens.refresh_token.as_ref().map(|t| self.encrypt(t)).transpose()?,
+			expires_at: tokens.expires_at,
+		})
+	}
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; 32]> {
+	BASE64
+		.decode(encoded.trim())
+		.ok()
+		.and_then(|key| key.try_into().ok())
+		.ok_or_else(|| Error::any("token key must be a base64 encoded 32-byte key"))
+}
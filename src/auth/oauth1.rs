@@ -0,0 +1,199 @@
+//! OAuth 1.0a user-context request signing.
+//!
+//! Some endpoints, notably the legacy media upload, still require OAuth 1.0a signatures instead of
+//! OAuth 2.0 bearer tokens.
+
+// std
+use std::env;
+// crates.io
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use hmac::{Hmac, Mac};
+use rand::{Rng, distributions::Alphanumeric};
+use reqwest::{
+	Request,
+	header::{AUTHORIZATION, CONTENT_TYPE, HeaderValue},
+};
+use sha1::Sha1;
+// self
+use crate::{auth::SecretString, prelude::*, time};
+
+/// OAuth 1.0a consumer and access token credentials used to sign requests with HMAC-SHA1.
+#[derive(Clone, Debug)]
+pub struct Oauth1Credentials {
+	/// Consumer (API) key of the app.
+	pub consumer_key: String,
+	/// Consumer (API) secret of the app.
+	pub consumer_secret: SecretString,
+	/// Access token of the user.
+	pub access_token: String,
+	/// Access token secret of the user.
+	pub access_token_secret: SecretString,
+}
+impl Oauth1Credentials {
+	/// Creates credentials from the app's consumer key pair and the user's access token pair.
+	pub fn new(
+		consumer_key: String,
+		consumer_secret: String,
+		access_token: String,
+		access_token_secret: String,
+	) -> Self {
+		Self {
+			consumer_key,
+			consumer_secret: consumer_secret.into(),
+			access_token,
+			access_token_secret: access_token_secret.into(),
+		}
+	}
+
+	/// Creates credentials from `X_CONSUMER_KEY`, `X_CONSUMER_SECRET`, `X_ACCESS_TOKEN` and
+	/// `X_ACCESS_TOKEN_SECRET`, returning `None` if any of them is not set.
+	pub fn from_env() -> Option<Self> {
+		Some(Self::new(
+			env::var("X_CONSUMER_KEY").ok()?,
+			env::var("X_CONSUMER_SECRET").ok()?,
+			env::var("X_ACCESS_TOKEN").ok()?,
+			env::var("X_ACCESS_TOKEN_SECRET").ok()?,
+		))
+	}
+
+	/// Signs the request by setting its `Authorization` header.
+	///
+	/// Query parameters and form encoded bodies are covered by the signature, JSON and multipart
+	/// bodies are not as mandated by the specification.
+	pub fn sign(&self, request: &mut Request) -> Result<()> {
+		let nonce =
+			rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect();
+		let timestamp = time::unix_now().to_string();
+		let authorization = self.authorization(request, nonce, timestamp);
+
+		request.headers_mut().insert(
+			AUTHORIZATION,
+			HeaderValue::from_str(&authorization)
+				.map_err(|e| Error::any(format!("invalid oauth 1.0a header: {e}")))?,
+		);
+
+		Ok(())
+	}
+
+	fn authorization(&self, request: &Request, nonce: String, timestamp: String) -> String {
+		let mut oauth_params = vec![
+			("oauth_consumer_key", self.consumer_key.clone()),
+			("oauth_nonce", nonce),
+			("oauth_signature_method", "HMAC-SHA1".into()),
+			("oauth_timestamp", timestamp),
+			("oauth_token", self.access_token.clone()),
+			("oauth_version", "1.0".into()),
+		];
+		let mut params = oauth_params
+			.iter()
+			.map(|(k, v)| (encode(k), encode(v)))
+			.chain(request.url().query_pairs().map(|(k, v)| (encode(&k), encode(&v))))
+			.collect::<Vec<_>>();
+		let is_form = request
+			.headers()
+			.get(CONTENT_TYPE)
+			.is_some_and(|v| v.as_bytes().starts_with(b"application/x-www-form-urlencoded"));
+
+		if is_form && let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+			params.extend(url::form_urlencoded::parse(body).map(|(k, v)| (encode(&k), encode(&v))));
+		}
+
+		params.sort();
+
+		let mut base_url = request.url().clone();
+
+		base_url.set_query(None);
+		base_url.set_fragment(None);
+
+		let params = params.into_iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>();
+		let base_string = format!(
+			"{}&{}&{}",
+			request.method().as_str(),
+			encode(base_url.as_str()),
+			encode(&params.join("&"))
+		);
+		let key = format!(
+			"{}&{}",
+			encode(self.consumer_secret.expose()),
+			encode(self.access_token_secret.expose())
+		);
+		let mut mac = <Hmac<Sha1>>::new_from_slice(key.as_bytes())
+			.expect("hmac accepts keys of any length; qed");
+
+		mac.update(base_string.as_bytes());
+		oauth_params.push(("oauth_signature", BASE64.encode(mac.finalize().into_bytes())));
+		oauth_params.sort();
+
+		let header = oauth_params
+			.into_iter()
+			.map(|(k, v)| format!("{k}=\"{}\"", encode(&v)))
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		format!("OAuth {header}")
+	}
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986.
+fn encode(s: &str) -> String {
+	urlencoding::encode(s).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+	// crates.io
+	use reqwest::{Method, Url};
+	// self
+	use super::*;
+
+	/// Reference request of X's "Creating a signature" guide.
+	fn reference_request() -> Request {
+		let url =
+			Url::parse("https://api.twitter.com/1.1/statuses/update.json?include_entities=true")
+				.unwrap();
+		let mut request = Request::new(Method::POST, url);
+
+		request
+			.headers_mut()
+			.insert(CONTENT_TYPE, HeaderValue::from_static("application/x-www-form-urlencoded"));
+		*request.body_mut() = Some(
+			"status=Hello%20Ladies%20%2b%20Gentlemen%2c%20a%20signed%20OAuth%20request%21".into(),
+		);
+
+		request
+	}
+
+	#[test]
+	fn authorization_matches_reference_signature() {
+		let credentials = Oauth1Credentials::new(
+			"xvz1evFS4wEEPTGEFPHBog".into(),
+			"kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw".into(),
+			"370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb".into(),
+			"LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE".into(),
+		);
+		let authorization = credentials.authorization(
+			&reference_request(),
+			"kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg".into(),
+			"1318622958".into(),
+		);
+
+		assert_eq!(
+			authorization,
+			"OAuth oauth_consumer_key=\"xvz1evFS4wEEPTGEFPHBog\", \
+			 oauth_nonce=\"kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg\", \
+			 oauth_signature=\"hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D\", \
+			 oauth_signature_method=\"HMAC-SHA1\", oauth_timestamp=\"1318622958\", \
+			 oauth_token=\"370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb\", \
+			 oauth_version=\"1.0\""
+		);
+	}
+
+	#[test]
+	fn encode_escapes_reserved_characters() {
+		assert_eq!(
+			encode("Ladies + Gentlemen, a signed OAuth request!"),
+			"Ladies%20%2B%20Gentlemen%2C%20a%20signed%20OAuth%20request%21"
+		);
+		assert_eq!(encode("-._~"), "-._~");
+	}
+}
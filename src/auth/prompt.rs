@@ -0,0 +1,62 @@
+//! Authorization code prompts used by the interactive OAuth 2.0 flow.
+
+// std
+use std::{io, process::Command};
+// self
+use crate::prelude::*;
+
+/// Collects the authorization code once the user has been sent to the authorization URL.
+///
+/// Implemented for async closures taking the URL and resolving to the code, so GUI apps, TUIs and
+/// web services can plug in their own way of collecting it.
+pub trait CodePrompt: Send + Sync {
+	/// Presents the authorization URL and resolves to the authorization code.
+	fn prompt<'a>(&'a self, auth_url: &'a str) -> BoxFuture<'a, Result<String>>;
+}
+impl<F, Fut> CodePrompt for F
+where
+	F: Send + Sync + Fn(String) -> Fut,
+	Fut: 'static + MaybeSend + Future<Output = Result<String>>,
+{
+	fn prompt<'a>(&'a self, auth_url: &'a str) -> BoxFuture<'a, Result<String>> {
+		Box::pin(self(auth_url.to_owned()))
+	}
+}
+
+/// Default prompt logging the authorization URL and reading the pasted code from stdin.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdinPrompt;
+impl CodePrompt for StdinPrompt {
+	fn prompt<'a>(&'a self, auth_url: &'a str) -> BoxFuture<'a, Result<String>> {
+		Box::pin(async move {
+			info!("open this url in your browser and paste the returned code: {auth_url}");
+
+			let mut code = String::new();
+
+			io::stdin().read_line(&mut code)?;
+
+			Ok(code)
+		})
+	}
+}
+
+/// Launches the system browser with the given URL.
+pub(crate) fn open_browser(url: &str) -> Result<()> {
+	#[cfg(target_os = "macos")]
+	let mut command = Command::new("open");
+	// Avoid `cmd /C start`, which would interpret the `&` separators of the query string.
+	#[cfg(target_os = "windows")]
+	let mut command = {
+		let mut command = Command::new("rundll32");
+
+		command.arg("url.dll,FileProtocolHandler");
+
+		command
+	};
+	#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+	let mut command = Command::new("xdg-open");
+
+	command.arg(url).spawn()?;
+
+	Ok(())
+}
@@ -0,0 +1,97 @@
+//! OAuth 2.0 scopes requested during user authorization.
+
+// std
+use std::{
+	collections::BTreeSet,
+	fmt::{Display, Formatter, Result as FmtResult},
+};
+// self
+use crate::prelude::*;
+
+/// Set of OAuth 2.0 scopes requested from the user during authorization.
+///
+/// Defaults to `tweet.read`, `tweet.write`, `users.read` and `offline.access`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scopes(BTreeSet<String>);
+impl Scopes {
+	pub const BLOCK_READ: &str = "block.read";
+	pub const BLOCK_WRITE: &str = "block.write";
+	pub const BOOKMARK_READ: &str = "bookmark.read";
+	pub const BOOKMARK_WRITE: &str = "bookmark.write";
+	pub const DM_READ: &str = "dm.read";
+	pub const DM_WRITE: &str = "dm.write";
+	pub const FOLLOWS_READ: &str = "follows.read";
+	pub const FOLLOWS_WRITE: &str = "follows.write";
+	pub const LIKE_READ: &str = "like.read";
+	pub const LIKE_WRITE: &str = "like.write";
+	pub const LIST_READ: &str = "list.read";
+	pub const LIST_WRITE: &str = "list.write";
+	pub const MEDIA_WRITE: &str = "media.write";
+	pub const MUTE_READ: &str = "mute.read";
+	pub const MUTE_WRITE: &str = "mute.write";
+	pub const OFFLINE_ACCESS: &str = "offline.access";
+	pub const SPACE_READ: &str = "space.read";
+	pub const TWEET_MODERATE_WRITE: &str = "tweet.moderate.write";
+	pub const TWEET_READ: &str = "tweet.read";
+	pub const TWEET_WRITE: &str = "tweet.write";
+	pub const USERS_READ: &str = "users.read";
+
+	/// Creates an empty scope set.
+	pub fn empty() -> Self {
+		Self(BTreeSet::new())
+	}
+
+	/// Adds a scope to the set.
+	pub fn with<S>(mut self, scope: S) -> Self
+	where
+		S: Into<String>,
+	{
+		self.0.insert(scope.into());
+
+		self
+	}
+
+	/// Returns whether the set contains the given scope.
+	pub fn contains(&self, scope: &str) -> bool {
+		self.0.contains(scope)
+	}
+
+	/// Iterates over the scopes in lexicographic order.
+	pub fn iter(&self) -> impl Iterator<Item = &str> {
+		self.0.iter().map(String::as_str)
+	}
+
+	/// Fails with [`Error::MissingScope`] on the first required scope not in the set.
+	pub fn ensure(&self, required: &[&str]) -> Result<()> {
+		if let Some(missing) = required.iter().find(|s| !self.contains(s)) {
+			Err(Error::MissingScope((*missing).to_owned()))?;
+		}
+
+		Ok(())
+	}
+}
+impl Default for Scopes {
+	fn default() -> Self {
+		Self::empty()
+			.with(Self::TWEET_READ)
+			.with(Self::TWEET_WRITE)
+			.with(Self::USERS_READ)
+			.with(Self::OFFLINE_ACCESS)
+	}
+}
+impl<S> FromIterator<S> for Scopes
+where
+	S: Into<String>,
+{
+	fn from_iter<I>(iter: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+	{
+		Self(iter.into_iter().map(Into::into).collect())
+	}
+}
+impl Display for Scopes {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		write!(f, "{}", self.iter().collect::<Vec<_>>().join(" "))
+	}
+}
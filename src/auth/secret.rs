@@ -0,0 +1,46 @@
+//! Secret string redacted in debug output.
+
+// std
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+// self
+use crate::prelude::*;
+
+/// String holding a secret such as a token, redacted in `Debug` output so logging the [`Api`]
+/// never leaks credentials.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+impl SecretString {
+	/// Wraps the secret.
+	pub fn new<S>(secret: S) -> Self
+	where
+		S: Into<String>,
+	{
+		Self(secret.into())
+	}
+
+	/// Returns the secret in plaintext.
+	pub fn expose(&self) -> &str {
+		&self.0
+	}
+
+	/// Consumes the wrapper and returns the secret in plaintext.
+	pub fn into_inner(self) -> String {
+		self.0
+	}
+}
+impl Debug for SecretString {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		f.write_str("SecretString(<redacted>)")
+	}
+}
+impl From<String> for SecretString {
+	fn from(secret: String) -> Self {
+		Self(secret)
+	}
+}
+impl From<&str> for SecretString {
+	fn from(secret: &str) -> Self {
+		Self(secret.to_owned())
+	}
+}
@@ -0,0 +1,383 @@
+//! Token persistence backends for the OAuth 2.0 authenticator.
+
+// std
+use std::{
+	env,
+	fmt::Debug,
+	fs::{self, OpenOptions},
+	hash::{BuildHasher, Hasher, RandomState},
+	io::{ErrorKind, Write},
+	path::{Path, PathBuf},
+	sync::Mutex,
+};
+// self
+use crate::{auth::SecretString, prelude::*};
+
+/// OAuth 2.0 tokens persisted by a [`TokenStore`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tokens {
+	/// Short-lived bearer access token.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub access_token: Option<SecretString>,
+	/// Long-lived refresh token used to obtain new access tokens.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub refresh_token: Option<SecretString>,
+	/// Unix timestamp in seconds at which the access token expires, if known.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub expires_at: Option<u64>,
+}
+
+/// Persistence backend consulted by the authenticator to load and save tokens.
+pub trait TokenStore: Debug + Send + Sync {
+	/// Loads previously persisted tokens, returning `None` if nothing has been stored yet.
+	fn load(&self) -> Result<Option<Tokens>>;
+
+	/// Persists the given tokens, replacing any previously stored ones.
+	fn save(&self, tokens: &Tokens) -> Result<()>;
+}
+
+/// Non-persistent token store keeping tokens in memory for the lifetime of the process.
+///
+/// Rotated refresh tokens are logged so they can be copied into `X_REFRESH_TOKEN` manually.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+	tokens: Mutex<Option<Tokens>>,
+}
+impl MemoryTokenStore {
+	/// Creates a memory store pre-populated with the given tokens.
+	pub fn new(tokens: Tokens) -> Self {
+		Self { tokens: Mutex::new(Some(tokens)) }
+	}
+
+	/// Creates a memory store seeded with the refresh token from `X_REFRESH_TOKEN`, if set.
+	pub fn from_env() -> Self {
+		let refresh_token = env::var("X_REFRESH_TOKEN").ok().map(SecretString::new);
+
+		Self { tokens: Mutex::new(Some(Tokens { refresh_token, ..Default::default() })) }
+	}
+}
+impl TokenStore for MemoryTokenStore {
+	fn load(&self) -> Result<Option<Tokens>> {
+		Ok(self.tokens.lock().expect("lock must not be poisoned; qed").clone())
+	}
+
+	fn save(&self, tokens: &Tokens) -> Result<()> {
+		let mut stored = self.tokens.lock().expect("lock must not be poisoned; qed");
+		let previous = stored.as_ref().and_then(|t| t.refresh_token.as_ref());
+
+		// Tokens are lost on exit, let user decide where to store the new refresh token. The token
+		// itself is never logged, it grants long-lived access to the account.
+		if let Some(refresh_token) = &tokens.refresh_token
+			&& previous != Some(refresh_token)
+		{
+			info!("🔑 new refresh token available, it will be lost on exit");
+			info!(
+				"💡 consider a persistent token store or reading it with `take_refresh_token` to update your X_REFRESH_TOKEN environment variable"
+			);
+		}
+
+		*stored = Some(tokens.to_owned());
+
+		Ok(())
+	}
+}
+
+/// Token store persisting tokens as a JSON file on the local filesystem.
+///
+/// Writes are atomic and, on Unix, the file is only readable by its owner (`0600`).
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+	path: PathBuf,
+}
+impl FileTokenStore {
+	/// Creates a file store backed by the given path, which is created on first save.
+	pub fn new<P>(path: P) -> Self
+	where
+		P: Into<PathBuf>,
+	{
+		Self { path: path.into() }
+	}
+
+	/// Returns the path of the backing file.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+impl TokenStore for FileTokenStore {
+	fn load(&self) -> Result<Option<Tokens>> {
+		match fs::read(&self.path) {
+			Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e)?,
+		}
+	}
+
+	fn save(&self, tokens: &Tokens) -> Result<()> {
+		write_atomic(&self.path, &serde_json::to_vec_pretty(tokens)?)
+	}
+}
+
+/// Token store keeping the refresh token in the `X_REFRESH_TOKEN` line of a `.env` file.
+///
+/// The line is rewritten atomically on rotation, leaving every other line untouched. Access tokens
+/// are short-lived and not persisted.
+#[derive(Clone, Debug)]
+pub struct DotenvTokenStore {
+	path: PathBuf,
+	key: String,
+}
+impl DotenvTokenStore {
+	/// Creates a store backed by the given `.env` file, which is created on first save.
+	pub fn new<P>(path: P) -> Self
+	where
+		P: Into<PathBuf>,
+	{
+		Self { path: path.into(), key: "X_REFRESH_TOKEN".into() }
+	}
+
+	/// Replaces the variable holding the refresh token, which defaults to `X_REFRESH_TOKEN`.
+	pub fn with_key<K>(mut self, key: K) -> Self
+	where
+		K: Into<String>,
+	{
+		self.key = key.into();
+
+		self
+	}
+
+	/// Returns the value if the line assigns the store's variable.
+	fn parse_line<'a>(&self, line: &'a str) -> Option<&'a str> {
+		let line = line.trim_start();
+		let line = line.strip_prefix("export ").unwrap_or(line);
+		let (key, value) = line.split_once('=')?;
+
+		(key.trim() == self.key).then(|| {
+			let value = value.trim();
+
+			match value.chars().next() {
+				Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+				// Unquoted values end at an inline comment.
+				_ => value.split(" #").next().unwrap_or_default().trim_end(),
+			}
+		})
+	}
+}
+impl TokenStore for DotenvTokenStore {
+	fn load(&self) -> Result<Option<Tokens>> {
+		let content = match fs::read_to_string(&self.path) {
+			Ok(content) => content,
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => Err(e)?,
+		};
+
+		Ok(content
+			.lines()
+			.find_map(|l| self.parse_line(l))
+			.filter(|v| !v.is_empty())
+			.map(|v| Tokens { refresh_token: Some(SecretString::new(v)), ..Default::default() }))
+	}
+
+	fn save(&self, tokens: &Tokens) -> Result<()> {
+		let content = match fs::read_to_string(&self.path) {
+			Ok(content) => content,
+			Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+			Err(e) => Err(e)?,
+		};
+		let mut lines =
+			content.lines().filter(|l| self.parse_line(l).is_none()).collect::<Vec<_>>();
+		let line =
+			tokens.refresh_token.as_ref().map(|t| format!("{}=\"{}\"", self.key, t.expose()));
+
+		if let Some(line) = &line {
+			// Keep the variable where it was to produce minimal diffs.
+			let pos = content
+				.lines()
+				.position(|l| self.parse_line(l).is_some())
+				.unwrap_or(lines.len())
+				.min(lines.len());
+
+			lines.insert(pos, line);
+		}
+
+		let mut content = lines.join("\n");
+
+		content.push('\n');
+
+		write_atomic(&self.path, content.as_bytes())
+	}
+}
+
+/// Token store persisting tokens in the platform secret store.
+///
+/// Backed by macOS Keychain, Windows Credential Manager or the Secret Service (GNOME Keyring,
+/// KWallet) over D-Bus on Linux, which persists across logins and reboots.
+#[cfg(feature = "keyring")]
+#[derive(Clone, Debug)]
+pub struct KeyringTokenStore {
+	service: String,
+	account: String,
+}
+#[cfg(feature = "keyring")]
+impl KeyringTokenStore {
+	/// Creates a keyring store for the given account under the `xv2api` service.
+	pub fn new<A>(account: A) -> Self
+	where
+		A: Into<String>,
+	{
+		Self::with_service("xv2api", account)
+	}
+
+	/// Creates a keyring store for the given service and account.
+	pub fn with_service<S, A>(service: S, account: A) -> Self
+	where
+		S: Into<String>,
+		A: Into<String>,
+	{
+		Self { service: service.into(), account: account.into() }
+	}
+
+	fn entry(&self) -> Result<keyring::Entry> {
+		Ok(keyring::Entry::new(&self.service, &self.account)?)
+	}
+}
+#[cfg(feature = "keyring")]
+impl TokenStore for KeyringTokenStore {
+	fn load(&self) -> Result<Option<Tokens>> {
+		match self.entry()?.get_password() {
+			Ok(secret) => Ok(Some(serde_json::from_str(&secret)?)),
+			Err(keyring::Error::NoEntry) => Ok(None),
+			Err(e) => Err(e)?,
+		}
+	}
+
+	fn save(&self, tokens: &Tokens) -> Result<()> {
+		self.entry()?.set_password(&serde_json::to_string(tokens)?)?;
+
+		Ok(())
+	}
+}
+
+/// Writes the file through a temporary sibling and renames it into place with owner-only access.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+	#[cfg(not(target_arch = "wasm32"))]
+	let pid = std::process::id();
+	#[cfg(target_arch = "wasm32")]
+	let pid = 0;
+	let nonce = RandomState::new().build_hasher().finish();
+	let mut tmp_path = path.to_owned().into_os_string();
+
+	// Unique per writer, so concurrent saves never share a file and a stale one left by a crash
+	// is never reopened with its old permissions.
+	tmp_path.push(format!(".{pid}.{nonce:016x}.tmp"));
+
+	let tmp_path = PathBuf::from(tmp_path);
+	let mut options = OpenOptions::new();
+
+	options.write(true).create_new(true);
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::OpenOptionsExt;
+
+		options.mode(0o600);
+	}
+
+	let mut file = options.open(&tmp_path)?;
+	let written = file
+		.write_all(bytes)
+		.and_then(|_| file.sync_all())
+		.and_then(|_| fs::rename(&tmp_path, path));
+
+	if written.is_err() {
+		let _ = fs::remove_file(&tmp_path);
+	}
+
+	Ok(written?)
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+	// self
+	use super::*;
+
+	fn env_file(name: &str, content: &str) -> PathBuf {
+		let path = env::temp_dir().join(format!("xv2api-{}-{name}.env", std::process::id()));
+
+		fs::write(&path, content).unwrap();
+
+		path
+	}
+
+	fn refresh_token(store: &DotenvTokenStore) -> Option<String> {
+		store.load().unwrap()?.refresh_token.map(|t| t.expose().to_owned())
+	}
+
+	#[test]
+	fn dotenv_parses_quoted_and_exported_values() {
+		let store = DotenvTokenStore::new("");
+
+		assert_eq!(store.parse_line("X_REFRESH_TOKEN=abc"), Some("abc"));
+		assert_eq!(store.parse_line("  X_REFRESH_TOKEN = abc  "), Some("abc"));
+		assert_eq!(store.parse_line("X_REFRESH_TOKEN=\"a b=c\""), Some("a b=c"));
+		assert_eq!(store.parse_line("X_REFRESH_TOKEN='abc' # rotated"), Some("abc"));
+		assert_eq!(store.parse_line("X_REFRESH_TOKEN=abc # rotated"), Some("abc"));
+		assert_eq!(store.parse_line("export X_REFRESH_TOKEN=\"abc\""), Some("abc"));
+		assert_eq!(store.parse_line("# X_REFRESH_TOKEN=abc"), None);
+		assert_eq!(store.parse_line("X_REFRESH_TOKEN_OLD=abc"), None);
+		assert_eq!(store.with_key("TOKEN").parse_line("TOKEN=abc"), Some("abc"));
+	}
+
+	#[test]
+	fn dotenv_loads_first_assignment() {
+		let path = env_file(
+			"load",
+			"# X_REFRESH_TOKEN=commented\nX_CLIENT_ID=id\nexport X_REFRESH_TOKEN='abc'\n",
+		);
+
+		assert_eq!(refresh_token(&DotenvTokenStore::new(&path)).as_deref(), Some("abc"));
+
+		fs::write(&path, "X_REFRESH_TOKEN=\n").unwrap();
+
+		assert_eq!(refresh_token(&DotenvTokenStore::new(&path)), None);
+
+		fs::remove_file(&path).unwrap();
+
+		assert_eq!(refresh_token(&DotenvTokenStore::new(&path)), None);
+	}
+
+	#[test]
+	fn dotenv_save_keeps_unrelated_lines() {
+		let path =
+			env_file("save", "# credentials\nX_CLIENT_ID=id\nX_REFRESH_TOKEN=old\nOTHER='x y'\n");
+		let store = DotenvTokenStore::new(&path);
+		let tokens = Tokens {
+			access_token: Some(SecretString::new("access")),
+			refresh_token: Some(SecretString::new("new")),
+			expires_at: Some(1),
+		};
+
+		store.save(&tokens).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(&path).unwrap(),
+			"# credentials\nX_CLIENT_ID=id\nX_REFRESH_TOKEN=\"new\"\nOTHER='x y'\n"
+		);
+		assert_eq!(refresh_token(&store).as_deref(), Some("new"));
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+
+			assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+		}
+
+		store.save(&Tokens::default()).unwrap();
+
+		assert_eq!(
+			fs::read_to_string(&path).unwrap(),
+			"# credentials\nX_CLIENT_ID=id\nOTHER='x y'\n"
+		);
+
+		fs::remove_file(&path).unwrap();
+	}
+}
@@ -0,0 +1,424 @@
+//! Synchronous API client for CLI tools and scripts.
+//!
+//! Wraps the async [`Api`](crate::Api) with a private single-threaded runtime, so it must not be
+//! used from within an async context.
+
+// std
+use std::sync::Arc;
+// crates.io
+use reqwest::Method;
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+// self
+use crate::{
+	ApiResponse, Envelope, Fields, Pagination, RequestOptions,
+	auth::{AuthProvider, Authenticator},
+	prelude::*,
+	search::{ApiSearch, DrainProgress, SearchParams},
+	timelines::{ApiTimeline, TimelineParams},
+	tweets::{ApiTweet, Conversation, TweetData, TweetRequest},
+	users::{ApiUser, UserObject},
+};
+
+/// Synchronous counterpart of [`Api`](crate::Api) blocking on every call.
+#[derive(Clone, Debug)]
+pub struct Api<A = Authenticator> {
+	inner: crate::Api<A>,
+	runtime: Arc<Runtime>,
+}
+impl Api {
+	/// Creates API client using credentials from environment variables, see
+	/// [`Api::from_env`](crate::Api::from_env).
+	pub fn from_env() -> Self {
+		Self::from_async(crate::Api::from_env())
+	}
+
+	/// Creates API client with provided OAuth 2.0 credentials.
+	#[cfg(feature = "oauth2")]
+	pub fn new(id: String, secret: String) -> Self {
+		Self::from_async(crate::Api::new(id, secret))
+	}
+
+	/// Creates API client with a pre-issued bearer token, bypassing OAuth entirely.
+	pub fn with_bearer_token(bearer_token: String) -> Self {
+		Self::from_async(crate::Api::with_bearer_token(bearer_token))
+	}
+
+	/// Creates API client with a pre-configured authenticator, sharing its HTTP client.
+	pub fn with_authenticator(authenticator: Authenticator) -> Self {
+		Self::from_async(crate::Api::with_authenticator(authenticator))
+	}
+}
+impl<A> Api<A>
+where
+	A: AuthProvider,
+{
+	/// Wraps an async API client, e.g. one configured through [`ApiBuilder`](crate::ApiBuilder).
+	pub fn from_async(inner: crate::Api<A>) -> Self {
+		let runtime = Builder::new_current_thread()
+			.enable_all()
+			.build()
+			.expect("runtime must be constructible; qed");
+
+		Self { inner, runtime: Arc::new(runtime) }
+	}
+
+	/// Returns the wrapped async API client.
+	pub fn as_async(&self) -> &crate::Api<A> {
+		&self.inner
+	}
+
+	/// Runs an arbitrary async call against the wrapped client to completion, e.g. for endpoints
+	/// without a blocking counterpart.
+	pub fn block_on<'a, F, Fut>(&'a self, f: F) -> Fut::Output
+	where
+		F: FnOnce(&'a crate::Api<A>) -> Fut,
+		Fut: Future,
+	{
+		self.runtime.block_on(f(&self.inner))
+	}
+
+	/// Sends GET requests with query parameters to API endpoints.
+	pub fn get<Q, T>(&self, url: &str, query: &Q) -> Result<T>
+	where
+		Q: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.block_on(|api| api.get(url, query))
+	}
+
+	/// Sends POST requests with JSON body to API endpoints.
+	pub fn post<B, T>(&self, url: &str, body: &B) -> Result<T>
+	where
+		B: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.block_on(|api| api.post(url, body))
+	}
+
+	/// Sends PUT requests with JSON body to API endpoints.
+	pub fn put<B, T>(&self, url: &str, body: &B) -> Result<T>
+	where
+		B: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.block_on(|api| api.put(url, body))
+	}
+
+	/// Sends DELETE requests without body to API endpoints.
+	pub fn delete<T>(&self, url: &str) -> Result<T>
+	where
+		T: for<'de> Deserialize<'de>,
+	{
+		self.block_on(|api| api.delete(url))
+	}
+
+	/// Sends a request to an endpoint not modeled by this crate yet, see
+	/// [`Api::request_raw`](crate::Api::request_raw).
+	pub fn request_raw(
+		&self,
+		method: Method,
+		path: &str,
+		query: &[(&str, &str)],
+		body: Option<&Value>,
+	) -> Result<Value> {
+		self.block_on(|api| api.request_raw(method, path, query, body))
+	}
+
+	/// Posts a tweet with the given text content.
+	pub fn tweet(&self, text: String) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.tweet(text))
+	}
+
+	/// Posts a tweet with the given text content, overriding the client configuration for this
+	/// call.
+	pub fn tweet_with_options(
+		&self,
+		text: String,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.tweet_with_options(text, options))
+	}
+
+	/// Posts a reply to the tweet with the given text content.
+	pub fn reply(
+		&self,
+		text: String,
+		tweet_id: String,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.reply(text, tweet_id))
+	}
+
+	/// Posts a reply to the tweet with the given text content, overriding the client
+	/// configuration for this call.
+	pub fn reply_with_options(
+		&self,
+		text: String,
+		tweet_id: String,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.reply_with_options(text, tweet_id, options))
+	}
+
+	/// Posts a tweet described by the request.
+	pub fn create_tweet(&self, request: TweetRequest) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.create_tweet(request))
+	}
+
+	/// Posts a tweet described by the request, overriding the client configuration for this
+	/// call.
+	pub fn create_tweet_with_options(
+		&self,
+		request: TweetRequest,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.create_tweet_with_options(request, options))
+	}
+
+	/// Looks up a tweet by ID with the requested fields and expansions.
+	pub fn get_tweet(&self, id: &str, fields: &Fields) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.get_tweet(id, fields))
+	}
+
+	/// Looks up a tweet by ID with the requested fields and expansions, overriding the client
+	/// configuration for this call.
+	pub fn get_tweet_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.get_tweet_with_options(id, fields, options))
+	}
+
+	/// Looks up a tweet by ID together with every version of its edit chain.
+	pub fn edit_history(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.edit_history(id, fields))
+	}
+
+	/// Looks up a tweet by ID together with every version of its edit chain, overriding the
+	/// client configuration for this call.
+	pub fn edit_history_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.block_on(|api| api.edit_history_with_options(id, fields, options))
+	}
+
+	/// Lists the tweets quoting the tweet, one page at a time.
+	pub fn quote_tweets(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.quote_tweets(tweet_id, pagination, fields))
+	}
+
+	/// Lists the tweets quoting the tweet, one page at a time, overriding the client
+	/// configuration for this call.
+	pub fn quote_tweets_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.quote_tweets_with_options(tweet_id, pagination, fields, options))
+	}
+
+	/// Lists the users who retweeted the tweet, one page at a time.
+	pub fn retweeted_by(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<UserObject>>>> {
+		self.block_on(|api| api.retweeted_by(tweet_id, pagination, fields))
+	}
+
+	/// Lists the users who retweeted the tweet, one page at a time, overriding the client
+	/// configuration for this call.
+	pub fn retweeted_by_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<UserObject>>>> {
+		self.block_on(|api| api.retweeted_by_with_options(tweet_id, pagination, fields, options))
+	}
+
+	/// Reconstructs the thread the tweet belongs to as a reply tree.
+	pub fn conversation(&self, tweet_id: &str, fields: &Fields) -> Result<Conversation> {
+		self.block_on(|api| api.conversation(tweet_id, fields))
+	}
+
+	/// Reconstructs the thread the tweet belongs to as a reply tree, overriding the client
+	/// configuration for this call.
+	pub fn conversation_with_options(
+		&self,
+		tweet_id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<Conversation> {
+		self.block_on(|api| api.conversation_with_options(tweet_id, fields, options))
+	}
+
+	/// Searches tweets of the last 7 days matching the query, one page at a time.
+	pub fn search_recent(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.search_recent(query, params, fields))
+	}
+
+	/// Searches tweets of the last 7 days matching the query, one page at a time, overriding the
+	/// client configuration for this call.
+	pub fn search_recent_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.search_recent_with_options(query, params, fields, options))
+	}
+
+	/// Searches tweets since 2006 matching the query, one page at a time.
+	pub fn search_all(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.search_all(query, params, fields))
+	}
+
+	/// Searches tweets since 2006 matching the query, one page at a time, overriding the client
+	/// configuration for this call.
+	pub fn search_all_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.search_all_with_options(query, params, fields, options))
+	}
+
+	/// Pages through every full-archive search result, handing each page to the callback.
+	pub fn drain_search_all<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		on_page: F,
+	) -> Result<DrainProgress>
+	where
+		F: Send + FnMut(Envelope<Vec<TweetData>>, &DrainProgress),
+	{
+		self.block_on(|api| api.drain_search_all(query, params, fields, on_page))
+	}
+
+	/// Pages through every full-archive search result, overriding the client configuration for
+	/// this call.
+	pub fn drain_search_all_with_options<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+		on_page: F,
+	) -> Result<DrainProgress>
+	where
+		F: Send + FnMut(Envelope<Vec<TweetData>>, &DrainProgress),
+	{
+		self.block_on(|api| {
+			api.drain_search_all_with_options(query, params, fields, options, on_page)
+		})
+	}
+
+	/// Lists the tweets posted by the user, newest first, one page at a time.
+	pub fn user_tweets(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.user_tweets(user_id, params, fields))
+	}
+
+	/// Lists the tweets posted by the user, newest first, one page at a time, overriding the
+	/// client configuration for this call.
+	pub fn user_tweets_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.user_tweets_with_options(user_id, params, fields, options))
+	}
+
+	/// Lists the tweets mentioning the user, newest first, one page at a time.
+	pub fn user_mentions(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.user_mentions(user_id, params, fields))
+	}
+
+	/// Lists the tweets mentioning the user, newest first, one page at a time, overriding the
+	/// client configuration for this call.
+	pub fn user_mentions_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.block_on(|api| api.user_mentions_with_options(user_id, params, fields, options))
+	}
+
+	/// Looks up the authenticated user with the requested fields and expansions.
+	pub fn me(&self, fields: &Fields) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.block_on(|api| api.me(fields))
+	}
+
+	/// Looks up the authenticated user with the requested fields and expansions, overriding the
+	/// client configuration for this call.
+	pub fn me_with_options(
+		&self,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.block_on(|api| api.me_with_options(fields, options))
+	}
+
+	/// Looks up a user by ID with the requested fields and expansions.
+	pub fn get_user(&self, id: &str, fields: &Fields) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.block_on(|api| api.get_user(id, fields))
+	}
+
+	/// Looks up a user by ID with the requested fields and expansions, overriding the client
+	/// configuration for this call.
+	pub fn get_user_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.block_on(|api| api.get_user_with_options(id, fields, options))
+	}
+}
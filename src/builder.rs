@@ -0,0 +1,381 @@
+//! Builder for configuring [`Api`] clients.
+
+// std
+use std::{collections::HashMap, sync::Arc, time::Duration};
+// crates.io
+#[cfg(not(target_arch = "wasm32"))] use reqwest::Proxy;
+use reqwest::{
+	Client,
+	header::{HeaderMap, HeaderValue, IntoHeaderName, USER_AGENT},
+};
+use url::Url;
+// self
+#[cfg(not(target_arch = "wasm32"))] use crate::auth::SecretString;
+use crate::{
+	DEFAULT_BASE_URL, HttpTransport, MetricsSink, Middleware, RateLimitBehavior, RetryPolicy,
+	auth::Authenticator,
+	cache::{CachePolicy, EtagCache, ResponseCache},
+	circuit_breaker::{CircuitBreaker, CircuitBreakerPolicy},
+	concurrency::ConcurrencyLimiter,
+	prelude::*,
+};
+
+/// Builder for [`Api`] clients, allowing a pre-configured HTTP client to be injected.
+///
+/// The HTTP client carries proxies, TLS settings, timeouts and connection pool tuning. Token
+/// requests of an [`Authenticator`] go through its own client, see
+/// [`Authenticator::with_http_client`].
+#[derive(Clone, Debug)]
+pub struct ApiBuilder<A = Authenticator> {
+	authenticator: Option<A>,
+	options: Options,
+}
+impl<A> ApiBuilder<A> {
+	/// Creates an empty builder.
+	pub fn new() -> Self {
+		Self { authenticator: None, options: Options::default() }
+	}
+
+	/// Sets the auth provider supplying bearer tokens.
+	pub fn authenticator<B>(self, authenticator: B) -> ApiBuilder<B> {
+		ApiBuilder { authenticator: Some(authenticator), options: self.options }
+	}
+
+	/// Sets the HTTP client used for API requests instead of a default one.
+	pub fn http_client(mut self, http: Client) -> Self {
+		self.options.http = Some(http);
+
+		self
+	}
+
+	/// Sets the base URL endpoint paths are resolved against, e.g. a local mock server or an API
+	/// gateway, instead of [`DEFAULT_BASE_URL`].
+	pub fn base_url<U>(mut self, url: U) -> Result<Self>
+	where
+		U: Into<String>,
+	{
+		let url = url.into();
+
+		Url::parse(&url)?;
+
+		self.options.base_url = Some(url.trim_end_matches('/').into());
+
+		Ok(self)
+	}
+
+	/// Sets the total timeout of each request, from connecting until the response body is read.
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.options.timeout = Some(timeout);
+
+		self
+	}
+
+	/// Sets the timeout for establishing connections.
+	///
+	/// Ignored if an HTTP client has been injected, configure it on that client instead.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+		self.options.connect_timeout = Some(timeout);
+
+		self
+	}
+
+	/// Sets the maximum number of idle connections kept per host.
+	///
+	/// Ignored if an HTTP client has been injected, configure it on that client instead.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+		self.options.pool_max_idle_per_host = Some(max);
+
+		self
+	}
+
+	/// Sets how long idle connections are kept in the pool, `None` keeping them forever.
+	///
+	/// Ignored if an HTTP client has been injected, configure it on that client instead.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+		self.options.pool_idle_timeout = Some(timeout);
+
+		self
+	}
+
+	/// Sets the interval of TCP keepalive probes, `None` disabling them.
+	///
+	/// Ignored if an HTTP client has been injected, configure it on that client instead.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+		self.options.tcp_keepalive = Some(interval);
+
+		self
+	}
+
+	/// Routes all requests through an HTTP(S) or SOCKS5 proxy, e.g. `http://proxy:3128` or
+	/// `socks5://proxy:1080`.
+	///
+	/// Ignored if an HTTP client has been injected, configure it on that client instead.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn proxy<U>(mut self, url: U) -> Result<Self>
+	where
+		U: AsRef<str>,
+	{
+		self.options.proxy = Some(Proxy::all(url.as_ref())?);
+
+		Ok(self)
+	}
+
+	/// Sets the credentials used to authenticate with the proxy.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn proxy_basic_auth<U, P>(mut self, username: U, password: P) -> Self
+	where
+		U: Into<String>,
+		P: Into<SecretString>,
+	{
+		self.options.proxy_auth = Some((username.into(), password.into()));
+
+		self
+	}
+
+	/// Sets the `User-Agent` identifying the application to X.
+	pub fn user_agent(self, user_agent: HeaderValue) -> Self {
+		self.default_header(USER_AGENT, user_agent)
+	}
+
+	/// Adds a header sent with every request, e.g. a tenant header required by a gateway.
+	///
+	/// Headers set through [`RequestOptions`](crate::RequestOptions) take precedence.
+	pub fn default_header<K>(mut self, name: K, value: HeaderValue) -> Self
+	where
+		K: IntoHeaderName,
+	{
+		self.options.default_headers.insert(name, value);
+
+		self
+	}
+
+	/// Adds headers sent with every request, replacing already set ones of the same name.
+	pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+		self.options.default_headers.extend(headers);
+
+		self
+	}
+
+	/// Caches the bodies of up to `capacity` GET responses along with their `ETag`, revalidating
+	/// them with `If-None-Match` and serving `304 Not Modified` responses from the cache.
+	pub fn etag_cache(mut self, capacity: usize) -> Self {
+		self.options.etag_cache = Some(capacity);
+
+		self
+	}
+
+	/// Serves repeated GET requests from an in-memory cache within the TTLs of the policy.
+	pub fn response_cache(mut self, policy: CachePolicy) -> Self {
+		self.options.response_cache = Some(policy);
+
+		self
+	}
+
+	/// Caps the number of requests in flight across all endpoints, queueing further ones.
+	///
+	/// A cap of `0` makes [`ApiBuilder::build`] fail, as no request could ever be sent.
+	pub fn max_concurrency(mut self, max: usize) -> Self {
+		self.options.max_concurrency = Some(max);
+
+		self
+	}
+
+	/// Caps the number of requests in flight to the endpoint class, e.g. `GET /2/tweets/:id`,
+	/// queueing further ones.
+	///
+	/// A cap of `0` makes [`ApiBuilder::build`] fail, as no request could ever be sent.
+	pub fn endpoint_concurrency<E>(mut self, endpoint: E, max: usize) -> Self
+	where
+		E: Into<String>,
+	{
+		self.options.endpoint_concurrency.insert(endpoint.into(), max);
+
+		self
+	}
+
+	/// Fails requests fast while an endpoint class keeps failing, see [`CircuitBreakerPolicy`].
+	pub fn circuit_breaker(mut self, policy: CircuitBreakerPolicy) -> Self {
+		self.options.circuit_breaker = Some(policy);
+
+		self
+	}
+
+	/// Sets the policy for retrying transient failures such as 5xx responses and connection
+	/// errors.
+	pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+		self.options.retry = retry;
+
+		self
+	}
+
+	/// Sets how requests rejected with `429 Too Many Requests` are handled.
+	pub fn rate_limit_behavior(mut self, rate_limit_behavior: RateLimitBehavior) -> Self {
+		self.options.rate_limit_behavior = rate_limit_behavior;
+
+		self
+	}
+
+	/// Registers a middleware run around every request, after the already registered ones.
+	pub fn middleware<M>(mut self, middleware: M) -> Self
+	where
+		M: 'static + Middleware,
+	{
+		self.options.middleware.push(Arc::new(middleware));
+
+		self
+	}
+
+	/// Sets the sink receiving request counts, latencies, retries and rate limit events.
+	pub fn metrics_sink<M>(mut self, metrics: M) -> Self
+	where
+		M: 'static + MetricsSink,
+	{
+		self.options.metrics = Some(Arc::new(metrics));
+
+		self
+	}
+
+	/// Logs every request and response including bodies at trace level, with the `Authorization`
+	/// header and token fields redacted, to debug mismatches against the live API.
+	pub fn debug_logging(mut self, debug_logging: bool) -> Self {
+		self.options.debug_logging = debug_logging;
+
+		self
+	}
+
+	/// Skips mutating requests, logging what would be sent and returning a synthetic success
+	/// response instead, see [`Api::with_dry_run`].
+	pub fn dry_run(mut self, dry_run: bool) -> Self {
+		self.options.dry_run = dry_run;
+
+		self
+	}
+
+	/// Replaces the transport sending requests, e.g. with a [`MockTransport`](crate::MockTransport)
+	/// in tests.
+	pub fn transport<T>(mut self, transport: T) -> Self
+	where
+		T: 'static + HttpTransport,
+	{
+		self.options.transport = Some(Arc::new(transport));
+
+		self
+	}
+
+	/// Builds the API client.
+	pub fn build(self) -> Result<Api<A>> {
+		let Some(authenticator) = self.authenticator else {
+			Err(Error::any("authenticator not set"))?
+		};
+		let options = self.options;
+
+		if options.max_concurrency == Some(0) {
+			Err(Error::any("max concurrency must be at least 1"))?;
+		}
+		if let Some((endpoint, _)) = options.endpoint_concurrency.iter().find(|(_, max)| **max == 0)
+		{
+			Err(Error::any(format!("concurrency of {endpoint} must be at least 1")))?;
+		}
+
+		let http = match options.http {
+			Some(http) => http,
+			None => {
+				#[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+				let mut builder = Client::builder();
+
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(timeout) = options.connect_timeout {
+					builder = builder.connect_timeout(timeout);
+				}
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(max) = options.pool_max_idle_per_host {
+					builder = builder.pool_max_idle_per_host(max);
+				}
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(timeout) = options.pool_idle_timeout {
+					builder = builder.pool_idle_timeout(timeout);
+				}
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(interval) = options.tcp_keepalive {
+					builder = builder.tcp_keepalive(interval);
+				}
+				#[cfg(not(target_arch = "wasm32"))]
+				if let Some(mut proxy) = options.proxy {
+					if let Some((username, password)) = &options.proxy_auth {
+						proxy = proxy.basic_auth(username, password.expose());
+					}
+
+					builder = builder.proxy(proxy);
+				}
+
+				builder.build()?
+			},
+		};
+
+		Ok(Api {
+			authenticator,
+			#[cfg(feature = "oauth1")]
+			oauth1: None,
+			transport: options.transport.unwrap_or_else(|| Arc::new(http.clone())),
+			http,
+			base_url: options.base_url.unwrap_or_else(|| DEFAULT_BASE_URL.into()),
+			timeout: options.timeout,
+			default_headers: options.default_headers,
+			retry: options.retry,
+			rate_limit_behavior: options.rate_limit_behavior,
+			rate_limits: Default::default(),
+			concurrency: ConcurrencyLimiter::new(
+				options.max_concurrency,
+				options.endpoint_concurrency,
+			),
+			circuit_breaker: options.circuit_breaker.map(CircuitBreaker::new),
+			etag_cache: options.etag_cache.map(EtagCache::new),
+			response_cache: options.response_cache.map(ResponseCache::new),
+			middleware: options.middleware,
+			metrics: options.metrics,
+			debug_logging: options.debug_logging,
+			dry_run: options.dry_run,
+		})
+	}
+}
+impl<A> Default for ApiBuilder<A> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+struct Options {
+	http: Option<Client>,
+	transport: Option<Arc<dyn HttpTransport>>,
+	base_url: Option<String>,
+	timeout: Option<Duration>,
+	default_headers: HeaderMap,
+	#[cfg(not(target_arch = "wasm32"))]
+	connect_timeout: Option<Duration>,
+	#[cfg(not(target_arch = "wasm32"))]
+	pool_max_idle_per_host: Option<usize>,
+	#[cfg(not(target_arch = "wasm32"))]
+	pool_idle_timeout: Option<Option<Duration>>,
+	#[cfg(not(target_arch = "wasm32"))]
+	tcp_keepalive: Option<Option<Duration>>,
+	#[cfg(not(target_arch = "wasm32"))]
+	proxy: Option<Proxy>,
+	#[cfg(not(target_arch = "wasm32"))]
+	proxy_auth: Option<(String, SecretString)>,
+	retry: RetryPolicy,
+	rate_limit_behavior: RateLimitBehavior,
+	max_concurrency: Option<usize>,
+	endpoint_concurrency: HashMap<String, usize>,
+	circuit_breaker: Option<CircuitBreakerPolicy>,
+	etag_cache: Option<usize>,
+	response_cache: Option<CachePolicy>,
+	middleware: Vec<Arc<dyn Middleware>>,
+	metrics: Option<Arc<dyn MetricsSink>>,
+	debug_logging: bool,
+	dry_run: bool,
+}
@@ -0,0 +1,151 @@
+//! Response caching.
+
+// std
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+// crates.io
+use reqwest::header::HeaderValue;
+// self
+use crate::time::Instant;
+
+/// Policy of the in-memory cache serving repeated GET requests locally within a time window.
+///
+/// Responses are cached per URL for the TTL of their endpoint class, e.g. `GET /2/tweets/:id`,
+/// falling back to the default TTL. Endpoint classes without any TTL are not cached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachePolicy {
+	/// Maximum number of cached responses, evicting the least recently used one once reached.
+	pub capacity: usize,
+	/// TTL of endpoint classes without a specific one.
+	pub default_ttl: Option<Duration>,
+	/// TTLs by endpoint class, e.g. `GET /2/users/by/username/:username`.
+	pub ttls: HashMap<String, Duration>,
+}
+impl CachePolicy {
+	/// Creates a policy caching up to `capacity` responses, without any TTL set yet.
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, default_ttl: None, ttls: HashMap::new() }
+	}
+
+	/// Sets the TTL of endpoint classes without a specific one.
+	pub fn default_ttl(mut self, ttl: Duration) -> Self {
+		self.default_ttl = Some(ttl);
+
+		self
+	}
+
+	/// Sets the TTL of the endpoint class, e.g. `GET /2/tweets/:id`.
+	pub fn ttl<E>(mut self, endpoint: E, ttl: Duration) -> Self
+	where
+		E: Into<String>,
+	{
+		self.ttls.insert(endpoint.into(), ttl);
+
+		self
+	}
+
+	fn ttl_of(&self, endpoint: &str) -> Option<Duration> {
+		self.ttls.get(endpoint).copied().or(self.default_ttl)
+	}
+}
+
+/// Bounded map evicting the least recently used entry once full.
+///
+/// Eviction scans all entries, which is cheap for the few thousand entries a client caches.
+#[derive(Debug)]
+struct Lru<V> {
+	capacity: usize,
+	tick: u64,
+	entries: HashMap<String, (u64, V)>,
+}
+impl<V> Lru<V>
+where
+	V: Clone,
+{
+	fn new(capacity: usize) -> Self {
+		Self { capacity, tick: 0, entries: HashMap::new() }
+	}
+
+	fn get(&mut self, key: &str) -> Option<V> {
+		self.tick += 1;
+
+		let (used, value) = self.entries.get_mut(key)?;
+
+		*used = self.tick;
+
+		Some(value.clone())
+	}
+
+	fn insert(&mut self, key: String, value: V) {
+		if self.capacity == 0 {
+			return;
+		}
+
+		self.tick += 1;
+
+		if !self.entries.contains_key(&key)
+			&& self.entries.len() >= self.capacity
+			&& let Some(lru) =
+				self.entries.iter().min_by_key(|(_, (used, _))| *used).map(|(k, _)| k.to_owned())
+		{
+			self.entries.remove(&lru);
+		}
+
+		self.entries.insert(key, (self.tick, value));
+	}
+}
+
+/// Bodies of GET responses keyed by URL along with their `ETag`, revalidated with
+/// `If-None-Match`.
+#[derive(Clone, Debug)]
+pub(crate) struct EtagCache(Arc<Mutex<Lru<(HeaderValue, String)>>>);
+impl EtagCache {
+	pub(crate) fn new(capacity: usize) -> Self {
+		Self(Arc::new(Mutex::new(Lru::new(capacity))))
+	}
+
+	/// Returns the `ETag` and body cached for the URL.
+	pub(crate) fn get(&self, url: &str) -> Option<(HeaderValue, String)> {
+		self.0.lock().expect("lock must not be poisoned; qed").get(url)
+	}
+
+	/// Caches the body of the URL under its `ETag`.
+	pub(crate) fn insert(&self, url: String, etag: HeaderValue, body: String) {
+		self.0.lock().expect("lock must not be poisoned; qed").insert(url, (etag, body));
+	}
+}
+
+/// Bodies of GET responses keyed by URL, served without a request until they expire.
+#[derive(Clone, Debug)]
+pub(crate) struct ResponseCache {
+	policy: Arc<CachePolicy>,
+	entries: Arc<Mutex<Lru<(Instant, String)>>>,
+}
+impl ResponseCache {
+	pub(crate) fn new(policy: CachePolicy) -> Self {
+		let entries = Arc::new(Mutex::new(Lru::new(policy.capacity)));
+
+		Self { policy: Arc::new(policy), entries }
+	}
+
+	/// Returns the body cached for the URL, unless it has expired.
+	pub(crate) fn get(&self, url: &str) -> Option<String> {
+		let (expires_at, body) =
+			self.entries.lock().expect("lock must not be poisoned; qed").get(url)?;
+
+		(Instant::now() < expires_at).then_some(body)
+	}
+
+	/// Caches the body of the URL for the TTL of its endpoint class, if any.
+	pub(crate) fn insert(&self, url: String, endpoint: &str, body: String) {
+		let Some(ttl) = self.policy.ttl_of(endpoint) else { return };
+
+		self.entries
+			.lock()
+			.expect("lock must not be poisoned; qed")
+			.insert(url, (Instant::now() + ttl, body));
+	}
+}
@@ -0,0 +1,101 @@
+//! Circuit breaker for failing endpoints.
+
+// std
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+// self
+use crate::{prelude::*, time::Instant};
+
+/// Policy of the circuit breaker failing requests fast while an endpoint class is degraded,
+/// instead of stampeding X with retries.
+///
+/// The circuit of an endpoint class opens after `failure_threshold` consecutive 5xx responses or
+/// timeouts. Once `cool_down` has passed, a single probe request is let through, closing the
+/// circuit on success and opening it again on failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitBreakerPolicy {
+	/// Number of consecutive failures opening the circuit.
+	pub failure_threshold: u32,
+	/// How long the circuit stays open before a probe request is let through.
+	pub cool_down: Duration,
+}
+impl Default for CircuitBreakerPolicy {
+	fn default() -> Self {
+		Self { failure_threshold: 5, cool_down: Duration::from_secs(30) }
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Circuit {
+	Closed { failures: u32 },
+	Open { until: Instant },
+	HalfOpen { since: Instant },
+}
+
+/// Circuits per endpoint class, shared across clones of [`Api`](crate::Api).
+#[derive(Clone, Debug)]
+pub(crate) struct CircuitBreaker {
+	policy: CircuitBreakerPolicy,
+	circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+impl CircuitBreaker {
+	pub(crate) fn new(policy: CircuitBreakerPolicy) -> Self {
+		Self { policy, circuits: Default::default() }
+	}
+
+	/// Fails with [`Error::CircuitOpen`] unless a request to the endpoint class may be sent.
+	pub(crate) fn allow(&self, endpoint: &str) -> Result<()> {
+		let mut circuits = self.circuits.lock().expect("lock must not be poisoned; qed");
+		let Some(circuit) = circuits.get_mut(endpoint) else {
+			return Ok(());
+		};
+		let now = Instant::now();
+
+		match *circuit {
+			Circuit::Closed { .. } => Ok(()),
+			Circuit::Open { until } if now >= until => {
+				*circuit = Circuit::HalfOpen { since: now };
+
+				Ok(())
+			},
+			// Let another probe through if the previous one never reported back, e.g. because
+			// its future was dropped.
+			Circuit::HalfOpen { since } if now >= since + self.policy.cool_down => {
+				*circuit = Circuit::HalfOpen { since: now };
+
+				Ok(())
+			},
+			Circuit::Open { until } => Err(Error::CircuitOpen {
+				endpoint: endpoint.into(),
+				retry_in: until.saturating_duration_since(now),
+			}),
+			Circuit::HalfOpen { since } => Err(Error::CircuitOpen {
+				endpoint: endpoint.into(),
+				retry_in: (since + self.policy.cool_down).saturating_duration_since(now),
+			}),
+		}
+	}
+
+	/// Records the outcome of a request to the endpoint class.
+	pub(crate) fn record(&self, endpoint: &str, failed: bool) {
+		let mut circuits = self.circuits.lock().expect("lock must not be poisoned; qed");
+
+		if !failed {
+			circuits.remove(endpoint);
+
+			return;
+		}
+
+		let circuit = circuits.entry(endpoint.into()).or_insert(Circuit::Closed { failures: 0 });
+		let open = Circuit::Open { until: Instant::now() + self.policy.cool_down };
+
+		*circuit = match *circuit {
+			Circuit::Closed { failures } if failures + 1 < self.policy.failure_threshold =>
+				Circuit::Closed { failures: failures + 1 },
+			_ => open,
+		};
+	}
+}
@@ -0,0 +1,41 @@
+//! In-flight request limiting.
+
+// std
+use std::{collections::HashMap, sync::Arc};
+// crates.io
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Caps the number of requests in flight, globally and per endpoint class.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConcurrencyLimiter {
+	global: Option<Arc<Semaphore>>,
+	endpoints: Arc<HashMap<String, Semaphore>>,
+}
+impl ConcurrencyLimiter {
+	pub(crate) fn new(global: Option<usize>, endpoints: HashMap<String, usize>) -> Self {
+		Self {
+			global: global.map(|max| Arc::new(Semaphore::new(max))),
+			endpoints: Arc::new(
+				endpoints
+					.into_iter()
+					.map(|(endpoint, max)| (endpoint, Semaphore::new(max)))
+					.collect(),
+			),
+		}
+	}
+
+	/// Waits until a request to the endpoint class may be sent, holding its slots until the
+	/// returned permits are dropped.
+	///
+	/// The endpoint class slot is acquired first, so requests queued on a busy endpoint class do
+	/// not hold global slots.
+	pub(crate) async fn acquire(&self, endpoint: &str) -> Vec<SemaphorePermit<'_>> {
+		let mut permits = Vec::new();
+
+		for semaphore in self.endpoints.get(endpoint).into_iter().chain(self.global.as_deref()) {
+			permits.push(semaphore.acquire().await.expect("semaphore is never closed; qed"));
+		}
+
+		permits
+	}
+}
@@ -0,0 +1,204 @@
+//! Response envelope shared by all endpoints.
+
+// std
+use std::{
+	error::Error as ErrorT,
+	fmt::{Display, Formatter, Result as FmtResult},
+};
+// crates.io
+use serde_json::Value;
+// self
+use crate::{
+	prelude::*,
+	tweets::{Place, Poll, TweetData},
+	users::UserObject,
+};
+
+/// Response envelope wrapping the primary `data` together with expanded objects, metadata and
+/// errors.
+///
+/// A body carrying errors but no `data`, or neither `data` nor `meta`, is not an envelope, so
+/// [`ApiResponse`](crate::ApiResponse) falls back to the error shape for it.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "RawEnvelope<T>", bound(deserialize = "T: Deserialize<'de>"))]
+pub struct Envelope<T> {
+	/// Primary object or objects returned by the endpoint, missing if nothing matched.
+	pub data: Option<T>,
+	/// Objects referenced by the primary data and requested through expansions.
+	#[serde(default)]
+	pub includes: Includes,
+	/// Metadata such as result counts and pagination tokens.
+	#[serde(default)]
+	pub meta: Meta,
+	/// Errors about individual objects returned alongside the data, e.g. protected or deleted
+	/// tweets in a bulk lookup.
+	#[serde(default)]
+	pub errors: Vec<PartialError>,
+}
+impl<T> TryFrom<RawEnvelope<T>> for Envelope<T> {
+	type Error = &'static str;
+
+	fn try_from(raw: RawEnvelope<T>) -> Result<Self, Self::Error> {
+		if raw.data.is_none() {
+			if !raw.errors.is_empty() {
+				return Err("errors without data");
+			}
+			// Empty results still carry `meta`, e.g. `{"meta":{"result_count":0}}`.
+			if raw.meta.is_none() {
+				return Err("neither data nor meta");
+			}
+		}
+
+		Ok(Self {
+			data: raw.data,
+			includes: raw.includes,
+			meta: raw.meta.unwrap_or_default(),
+			errors: raw.errors,
+		})
+	}
+}
+impl<T> Envelope<T> {
+	/// Returns whether some objects failed while others were returned.
+	pub fn is_partial(&self) -> bool {
+		self.data.is_some() && !self.errors.is_empty()
+	}
+
+	/// Returns the primary data, or an error if nothing matched.
+	pub fn into_data(self) -> Result<T> {
+		self.data.ok_or_else(|| Error::any("response has no data"))
+	}
+}
+
+/// Wire shape of [`Envelope`], validated to be a success rather than any JSON object.
+#[derive(Deserialize)]
+struct RawEnvelope<T> {
+	data: Option<T>,
+	#[serde(default)]
+	includes: Includes,
+	meta: Option<Meta>,
+	#[serde(default)]
+	errors: Vec<PartialError>,
+}
+
+/// Objects included through expansions.
+#[derive(Debug, Default, Deserialize)]
+pub struct Includes {
+	/// Expanded users, e.g. tweet authors.
+	#[serde(default)]
+	pub users: Vec<UserObject>,
+	/// Expanded tweets, e.g. referenced or pinned tweets.
+	#[serde(default)]
+	pub tweets: Vec<TweetData>,
+	/// Expanded media attachments.
+	#[serde(default)]
+	pub media: Vec<Value>,
+	/// Expanded polls.
+	#[serde(default)]
+	pub polls: Vec<Poll>,
+	/// Expanded places.
+	#[serde(default)]
+	pub places: Vec<Place>,
+}
+
+/// Response metadata.
+#[derive(Debug, Default, Deserialize)]
+pub struct Meta {
+	/// Number of objects returned in this page.
+	pub result_count: Option<u32>,
+	/// Token of the next page, missing on the last page.
+	pub next_token: Option<String>,
+	/// Token of the previous page, missing on the first page.
+	pub previous_token: Option<String>,
+	/// ID of the most recent object in this page.
+	pub newest_id: Option<String>,
+	/// ID of the oldest object in this page.
+	pub oldest_id: Option<String>,
+}
+
+/// Error about an individual object returned with a successful response.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PartialError {
+	/// Brief title or category of the error, e.g. `Not Found Error`, empty for request validation
+	/// errors.
+	#[serde(default)]
+	pub title: String,
+	/// Detailed description of the error.
+	#[serde(alias = "message")]
+	pub detail: Option<String>,
+	/// URI reference identifying the error type.
+	pub r#type: Option<String>,
+	/// Type of the failed resource, e.g. `tweet` or `user`.
+	pub resource_type: Option<String>,
+	/// ID of the failed resource.
+	pub resource_id: Option<String>,
+	/// Request parameter referring to the failed resource, e.g. `ids`.
+	pub parameter: Option<String>,
+	/// Value of the parameter referring to the failed resource.
+	pub value: Option<Value>,
+	/// Invalid request parameters with their values, e.g. `{"id":["abc"]}`.
+	pub parameters: Option<Value>,
+}
+impl Display for PartialError {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		write!(f, "{}", self.title)?;
+
+		if let Some(id) = &self.resource_id {
+			write!(f, " ({id})")?;
+		}
+		if let Some(detail) = &self.detail {
+			if self.title.is_empty() && self.resource_id.is_none() {
+				write!(f, "{detail}")?;
+			} else {
+				write!(f, ": {detail}")?;
+			}
+		}
+
+		Ok(())
+	}
+}
+impl ErrorT for PartialError {}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+	use crate::ApiResponse;
+
+	fn parse(json: &str) -> ApiResponse<Envelope<TweetData>> {
+		serde_json::from_str(json).unwrap()
+	}
+
+	#[test]
+	fn envelope_accepts_data_and_empty_results() {
+		let ApiResponse::Ok(envelope) = parse(
+			r#"{"data":{"id":"1","text":"a"},"errors":[{"title":"Not Found Error","resource_id":"2"}]}"#,
+		) else {
+			panic!("expected an envelope");
+		};
+
+		assert!(envelope.is_partial());
+
+		let envelope = parse(r#"{"meta":{"result_count":0}}"#).into_result().unwrap();
+
+		assert!(envelope.data.is_none());
+		assert_eq!(envelope.meta.result_count, Some(0));
+	}
+
+	#[test]
+	fn envelope_rejects_error_bodies() {
+		let e = parse(
+			r#"{"errors":[{"title":"Not Found Error","detail":"Could not find tweet with id: [1].","type":"https://api.twitter.com/2/problems/resource-not-found","resource_id":"1"}]}"#,
+		)
+		.into_result()
+		.unwrap_err();
+
+		assert!(matches!(e, Error::Api { .. }));
+		assert!(matches!(
+			parse(
+				r#"{"title":"Unauthorized","type":"about:blank","status":401,"detail":"Unauthorized"}"#
+			),
+			ApiResponse::Err(_)
+		));
+		assert!(serde_json::from_str::<ApiResponse<Envelope<TweetData>>>(r#"{"foo":1}"#).is_err());
+	}
+}
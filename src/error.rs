@@ -1,7 +1,13 @@
 #![allow(missing_docs)]
 
 // std
-use std::borrow::Cow;
+use std::{
+	borrow::Cow,
+	fmt::{Display, Formatter, Result as FmtResult},
+	time::Duration,
+};
+// crates.io
+use reqwest::{StatusCode, header::HeaderMap};
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -13,6 +19,10 @@ pub enum Error {
 	#[error(transparent)]
 	Io(#[from] std::io::Error),
 
+	#[cfg(feature = "keyring")]
+	#[error(transparent)]
+	Keyring(#[from] keyring::Error),
+	#[cfg(feature = "oauth2")]
 	#[error(transparent)]
 	Oauth2(
 		#[from]
@@ -21,21 +31,57 @@ pub enum Error {
 			oauth2::StandardErrorResponse<oauth2::basic::BasicErrorResponseType>,
 		>,
 	),
+	#[cfg(feature = "oauth2")]
+	#[error(transparent)]
+	Oauth2Configuration(#[from] oauth2::ConfigurationError),
+	#[cfg(feature = "oauth2")]
+	#[error(transparent)]
+	Oauth2Revocation(
+		#[from]
+		oauth2::RequestTokenError<
+			oauth2::HttpClientError<reqwest::Error>,
+			oauth2::StandardErrorResponse<oauth2::RevocationErrorResponseType>,
+		>,
+	),
 	#[error(transparent)]
 	Reqwest(#[from] reqwest::Error),
 	#[error(transparent)]
 	SerdeJson(#[from] serde_json::Error),
-
 	#[error(transparent)]
-	Api(#[from] crate::ApiError),
+	Url(#[from] url::ParseError),
+
+	/// Without a response if X returned the error with a successful status.
+	#[error("{api_error}")]
+	Api { api_error: Box<crate::ApiError>, response: Option<Box<ErrorResponse>> },
 	#[error("authentication failed")]
 	AuthenticationFailed,
+	#[error("circuit open for {endpoint}, retry in {retry_in:?}")]
+	CircuitOpen { endpoint: String, retry_in: Duration },
+	#[error("failed to deserialize {type_name} from {endpoint}: {source}; body: {body}")]
+	Deserialize {
+		endpoint: String,
+		type_name: &'static str,
+		/// Start of the body with token fields redacted.
+		body: String,
+		source: serde_json::Error,
+	},
+	/// The access level of the app or the user does not allow the request, e.g. a write-disabled
+	/// app or an action on a protected account.
+	#[error("forbidden: {response}")]
+	Forbidden { api_error: Option<Box<crate::ApiError>>, response: Box<ErrorResponse> },
+	#[error("{response}")]
+	Http { response: Box<ErrorResponse> },
+	#[error("missing scope: {0}")]
+	MissingScope(String),
 	#[error("oauth required")]
 	OauthRequired,
+	/// With the window parsed from the `x-rate-limit-*` headers, if X sent them.
 	#[error("rate limit exceeded")]
-	RateLimit,
+	RateLimit { rate_limit: Option<crate::RateLimit>, response: Box<ErrorResponse> },
+	/// Without a response if the token was rejected before, e.g. a static one that cannot be
+	/// refreshed.
 	#[error("unauthorized")]
-	Unauthorized,
+	Unauthorized { response: Option<Box<ErrorResponse>> },
 }
 impl Error {
 	pub fn any<T>(any: T) -> Self
@@ -44,4 +90,110 @@ impl Error {
 	{
 		Self::Any(any.into())
 	}
+
+	/// Returns the response rejected by X, if the error stems from one.
+	pub fn response(&self) -> Option<&ErrorResponse> {
+		match self {
+			Self::Forbidden { response, .. }
+			| Self::Http { response }
+			| Self::RateLimit { response, .. } => Some(response),
+			Self::Api { response, .. } | Self::Unauthorized { response } => response.as_deref(),
+			_ => None,
+		}
+	}
+
+	/// Returns whether retrying the same request later may succeed, e.g. on rate limits, 5xx
+	/// responses and connection failures.
+	pub fn is_retryable(&self) -> bool {
+		match self {
+			Self::CircuitOpen { .. } | Self::RateLimit { .. } => true,
+			Self::Reqwest(e) => crate::RetryPolicy::default().is_retryable_error(e),
+			#[cfg(feature = "oauth2")]
+			Self::Oauth2(oauth2::RequestTokenError::Request(_)) => true,
+			_ => self.response().is_some_and(|r| r.status.is_server_error()),
+		}
+	}
+
+	/// Returns whether the credentials are missing, invalid or lack permissions, which retrying
+	/// cannot fix.
+	pub fn is_auth(&self) -> bool {
+		#[cfg(feature = "oauth2")]
+		if let Self::Oauth2(oauth2::RequestTokenError::ServerResponse(_)) = self {
+			return true;
+		}
+
+		matches!(
+			self,
+			Self::AuthenticationFailed
+				| Self::MissingScope(_)
+				| Self::Forbidden { .. }
+				| Self::OauthRequired
+				| Self::Unauthorized { .. }
+		)
+	}
+
+	/// Returns whether X rejected the request due to its rate limits.
+	pub fn is_rate_limited(&self) -> bool {
+		matches!(self, Self::RateLimit { .. })
+	}
+
+	/// Returns how long to wait before retrying, from the rate limit window or the `retry-after`
+	/// header.
+	pub fn retry_after(&self) -> Option<Duration> {
+		match self {
+			Self::CircuitOpen { retry_in, .. } => return Some(*retry_in),
+			Self::RateLimit { rate_limit: Some(rate_limit), .. } =>
+				return Some(rate_limit.reset_in()),
+			_ => (),
+		}
+
+		self.response()?
+			.headers
+			.get("retry-after")?
+			.to_str()
+			.ok()?
+			.trim()
+			.parse()
+			.ok()
+			.map(Duration::from_secs)
+	}
+}
+
+/// Response rejected by X, kept for post-mortem debugging.
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+	/// Status code of the response.
+	pub status: StatusCode,
+	/// Rate limit, `retry-after` and `x-transaction-id` headers of the response.
+	pub headers: HeaderMap,
+	/// Raw body of the response.
+	pub body: String,
+}
+impl ErrorResponse {
+	/// Keeps the headers relevant for debugging out of all response headers.
+	pub(crate) fn new(status: StatusCode, headers: &HeaderMap, body: String) -> Self {
+		let headers = headers
+			.iter()
+			.filter(|(name, _)| {
+				let name = name.as_str();
+
+				name.starts_with("x-rate-limit-")
+					|| name == "x-transaction-id"
+					|| name == "retry-after"
+			})
+			.map(|(name, value)| (name.to_owned(), value.to_owned()))
+			.collect();
+
+		Self { status, headers, body }
+	}
+
+	/// Returns the `x-transaction-id` X support asks for when reporting issues.
+	pub fn transaction_id(&self) -> Option<&str> {
+		self.headers.get("x-transaction-id").and_then(|id| id.to_str().ok())
+	}
+}
+impl Display for ErrorResponse {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		write!(f, "{}: {}", self.status, self.body)
+	}
 }
@@ -0,0 +1,291 @@
+//! Fields and expansions requested from GET endpoints.
+
+// std
+use std::{
+	collections::BTreeSet,
+	fmt::{Display, Formatter, Result as FmtResult},
+	str::FromStr,
+};
+// crates.io
+use serde::{Deserializer, Serializer, de::Error as DeError};
+// self
+use crate::prelude::*;
+
+macro_rules! fields {
+	($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal,)* }) => {
+		$(#[$meta])*
+		#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+		pub enum $name {
+			$(
+				#[doc = concat!("`", $value, "`")]
+				$variant,
+			)*
+		}
+		impl $name {
+			/// Returns the name sent to the API.
+			pub fn as_str(&self) -> &'static str {
+				match self {
+					$(Self::$variant => $value,)*
+				}
+			}
+		}
+		impl Display for $name {
+			fn fmt(&self, f: &mut Formatter) -> FmtResult {
+				f.write_str(self.as_str())
+			}
+		}
+	};
+}
+
+fields! {
+	/// Field of tweet objects requested through `tweet.fields`.
+	TweetField {
+		Article => "article",
+		Attachments => "attachments",
+		AuthorId => "author_id",
+		CardUri => "card_uri",
+		CommunityId => "community_id",
+		ContextAnnotations => "context_annotations",
+		ConversationId => "conversation_id",
+		CreatedAt => "created_at",
+		DisplayTextRange => "display_text_range",
+		EditControls => "edit_controls",
+		EditHistoryTweetIds => "edit_history_tweet_ids",
+		Entities => "entities",
+		Geo => "geo",
+		Id => "id",
+		InReplyToUserId => "in_reply_to_user_id",
+		Lang => "lang",
+		MediaMetadata => "media_metadata",
+		NonPublicMetrics => "non_public_metrics",
+		NoteTweet => "note_tweet",
+		OrganicMetrics => "organic_metrics",
+		PossiblySensitive => "possibly_sensitive",
+		PromotedMetrics => "promoted_metrics",
+		PublicMetrics => "public_metrics",
+		ReferencedTweets => "referenced_tweets",
+		ReplySettings => "reply_settings",
+		Scopes => "scopes",
+		Source => "source",
+		Text => "text",
+		Withheld => "withheld",
+	}
+}
+fields! {
+	/// Field of user objects requested through `user.fields`.
+	UserField {
+		Affiliation => "affiliation",
+		ConnectionStatus => "connection_status",
+		CreatedAt => "created_at",
+		Description => "description",
+		Entities => "entities",
+		Id => "id",
+		Location => "location",
+		MostRecentTweetId => "most_recent_tweet_id",
+		Name => "name",
+		PinnedTweetId => "pinned_tweet_id",
+		ProfileBannerUrl => "profile_banner_url",
+		ProfileImageUrl => "profile_image_url",
+		Protected => "protected",
+		PublicMetrics => "public_metrics",
+		ReceivesYourDm => "receives_your_dm",
+		SubscriptionType => "subscription_type",
+		Url => "url",
+		Username => "username",
+		Verified => "verified",
+		VerifiedType => "verified_type",
+		Withheld => "withheld",
+	}
+}
+fields! {
+	/// Field of media objects requested through `media.fields`.
+	MediaField {
+		AltText => "alt_text",
+		DurationMs => "duration_ms",
+		Height => "height",
+		MediaKey => "media_key",
+		NonPublicMetrics => "non_public_metrics",
+		OrganicMetrics => "organic_metrics",
+		PreviewImageUrl => "preview_image_url",
+		PromotedMetrics => "promoted_metrics",
+		PublicMetrics => "public_metrics",
+		Type => "type",
+		Url => "url",
+		Variants => "variants",
+		Width => "width",
+	}
+}
+fields! {
+	/// Field of poll objects requested through `poll.fields`.
+	PollField {
+		DurationMinutes => "duration_minutes",
+		EndDatetime => "end_datetime",
+		Id => "id",
+		Options => "options",
+		VotingStatus => "voting_status",
+	}
+}
+fields! {
+	/// Field of place objects requested through `place.fields`.
+	PlaceField {
+		ContainedWithin => "contained_within",
+		Country => "country",
+		CountryCode => "country_code",
+		FullName => "full_name",
+		Geo => "geo",
+		Id => "id",
+		Name => "name",
+		PlaceType => "place_type",
+	}
+}
+fields! {
+	/// Referenced object included in the response through `expansions`.
+	Expansion {
+		ArticleCoverMedia => "article.cover_media",
+		ArticleMediaEntities => "article.media_entities",
+		AttachmentsMediaKeys => "attachments.media_keys",
+		AttachmentsPollIds => "attachments.poll_ids",
+		AuthorId => "author_id",
+		EditHistoryTweetIds => "edit_history_tweet_ids",
+		EntitiesMentionsUsername => "entities.mentions.username",
+		GeoPlaceId => "geo.place_id",
+		InReplyToUserId => "in_reply_to_user_id",
+		PinnedTweetId => "pinned_tweet_id",
+		ReferencedTweetsId => "referenced_tweets.id",
+		ReferencedTweetsIdAuthorId => "referenced_tweets.id.author_id",
+	}
+}
+
+/// Fields and expansions requested from GET endpoints, serialized into the `*.fields` and
+/// `expansions` query parameters.
+///
+/// Without them, most endpoints only return object IDs and texts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Fields {
+	#[serde(
+		rename = "tweet.fields",
+		serialize_with = "comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	tweet: BTreeSet<TweetField>,
+	#[serde(
+		rename = "user.fields",
+		serialize_with = "comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	user: BTreeSet<UserField>,
+	#[serde(
+		rename = "media.fields",
+		serialize_with = "comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	media: BTreeSet<MediaField>,
+	#[serde(
+		rename = "poll.fields",
+		serialize_with = "comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	poll: BTreeSet<PollField>,
+	#[serde(
+		rename = "place.fields",
+		serialize_with = "comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	place: BTreeSet<PlaceField>,
+	#[serde(serialize_with = "comma_separated", skip_serializing_if = "BTreeSet::is_empty")]
+	expansions: BTreeSet<Expansion>,
+}
+impl Fields {
+	/// Creates an empty set requesting the default fields only.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Requests the given tweet fields.
+	pub fn tweet_fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = TweetField>,
+	{
+		self.tweet.extend(fields);
+
+		self
+	}
+
+	/// Requests the given user fields.
+	pub fn user_fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = UserField>,
+	{
+		self.user.extend(fields);
+
+		self
+	}
+
+	/// Requests the given media fields.
+	pub fn media_fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = MediaField>,
+	{
+		self.media.extend(fields);
+
+		self
+	}
+
+	/// Requests the given poll fields.
+	pub fn poll_fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = PollField>,
+	{
+		self.poll.extend(fields);
+
+		self
+	}
+
+	/// Requests the given place fields.
+	pub fn place_fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = PlaceField>,
+	{
+		self.place.extend(fields);
+
+		self
+	}
+
+	/// Requests the given expansions.
+	pub fn expansions<I>(mut self, expansions: I) -> Self
+	where
+		I: IntoIterator<Item = Expansion>,
+	{
+		self.expansions.extend(expansions);
+
+		self
+	}
+
+	/// Returns whether no fields or expansions are requested.
+	pub fn is_empty(&self) -> bool {
+		self == &Self::default()
+	}
+}
+
+pub(crate) fn comma_separated<S, T>(values: &BTreeSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer,
+	T: Display,
+{
+	serializer.collect_str(&values.iter().map(ToString::to_string).collect::<Vec<_>>().join(","))
+}
+
+pub(crate) fn from_comma_separated<'de, D, T>(deserializer: D) -> Result<BTreeSet<T>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Ord + FromStr,
+	T::Err: Display,
+{
+	let values = String::deserialize(deserializer)?;
+
+	values
+		.split(',')
+		.filter(|v| !v.is_empty())
+		.map(|v| v.parse().map_err(D::Error::custom))
+		.collect()
+}
@@ -2,163 +2,862 @@
 
 // #![deny(clippy::all, missing_docs, unused_crate_dependencies)]
 
+#[macro_use]
+mod macros;
+
+pub mod account;
 pub mod auth;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))] pub mod blocking;
 pub mod error;
+pub mod search;
+pub mod timelines;
 pub mod tweets;
+pub mod users;
+
+mod builder;
+pub use builder::*;
+mod cache;
+pub use cache::*;
+mod circuit_breaker;
+pub use circuit_breaker::*;
+mod concurrency;
+mod envelope;
+pub use envelope::*;
+mod fields;
+pub use fields::*;
+mod links;
+mod logging;
+mod metrics;
+pub use metrics::*;
+mod middleware;
+pub use middleware::*;
+mod options;
+pub use options::*;
+mod pagination;
+pub use pagination::*;
+mod rate_limit;
+pub use rate_limit::*;
+mod raw;
+pub use raw::*;
+#[cfg(all(feature = "replay", not(target_arch = "wasm32")))] mod replay;
+#[cfg(all(feature = "replay", not(target_arch = "wasm32")))] pub use replay::*;
+mod retry;
+pub use retry::*;
+mod sync;
+pub use sync::*;
+mod time;
+pub use time::Timestamp;
+mod transport;
+pub use transport::*;
 
 mod prelude {
 	pub use serde::{Deserialize, Serialize};
 	pub use std::future::Future;
 
-	pub(crate) use crate::{Api, error::*};
+	pub(crate) use crate::{Api, BoxFuture, MaybeSend, error::*};
 }
 use prelude::*;
 
 // std
 use std::{
+	collections::HashMap,
 	env,
 	error::Error as ErrorT,
 	fmt::{Display, Formatter, Result as FmtResult},
+	pin::Pin,
+	sync::Arc,
+	time::Duration,
 };
 // crates.io
 use reqwest::{
-	Client, RequestBuilder, Response,
-	header::{AUTHORIZATION, CONTENT_TYPE},
+	Client, Method, Request, RequestBuilder, Response, StatusCode, Url,
+	header::{AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderMap, HeaderValue, IF_NONE_MATCH},
 };
+use serde_json::Value;
 // self
-use auth::Authenticator;
+use auth::{AuthProvider, Authenticator};
+use concurrency::ConcurrencyLimiter;
+use time::Instant;
+
+/// Default base URL of the X API.
+pub const DEFAULT_BASE_URL: &str = "https://api.x.com";
+
+/// Boxed future used by object-safe extension traits.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn 'a + Send + Future<Output = T>>>;
+/// Boxed future used by object-safe extension traits.
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn 'a + Future<Output = T>>>;
+
+/// `Send` on native targets, where futures may move between threads, and no bound on WASM, where
+/// HTTP futures are bound to the single JS thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T> MaybeSend for T where T: ?Sized + Send {}
+/// `Send` on native targets, where futures may move between threads, and no bound on WASM, where
+/// HTTP futures are bound to the single JS thread.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T where T: ?Sized {}
 
 /// Main API client for interacting with X/Twitter v2 API endpoints.
+///
+/// Generic over the [`AuthProvider`] supplying bearer tokens, defaulting to the built-in OAuth 2.0
+/// [`Authenticator`].
 #[derive(Clone, Debug)]
-pub struct Api {
-	/// OAuth 2.0 authenticator for managing bearer tokens.
-	pub authenticator: Authenticator,
+pub struct Api<A = Authenticator> {
+	/// Provider of bearer tokens, the OAuth 2.0 authenticator by default.
+	pub authenticator: A,
+	/// OAuth 1.0a credentials signing every request instead of a bearer token, if set.
+	#[cfg(feature = "oauth1")]
+	pub oauth1: Option<auth::oauth1::Oauth1Credentials>,
 	http: Client,
+	transport: Arc<dyn HttpTransport>,
+	base_url: String,
+	timeout: Option<Duration>,
+	default_headers: HeaderMap,
+	retry: RetryPolicy,
+	rate_limit_behavior: RateLimitBehavior,
+	rate_limits: RateLimitTracker,
+	concurrency: ConcurrencyLimiter,
+	circuit_breaker: Option<CircuitBreaker>,
+	etag_cache: Option<EtagCache>,
+	response_cache: Option<ResponseCache>,
+	middleware: Vec<Arc<dyn Middleware>>,
+	metrics: Option<Arc<dyn MetricsSink>>,
+	debug_logging: bool,
+	dry_run: bool,
 }
 impl Api {
+	/// Creates a builder for API clients with custom HTTP configuration.
+	pub fn builder() -> ApiBuilder {
+		ApiBuilder::new()
+	}
+
 	/// Creates API client using credentials from environment variables.
+	///
+	/// `X_BEARER_TOKEN` takes precedence and bypasses OAuth entirely, otherwise `X_CLIENT_ID` is
+	/// required and a public client is used if `X_CLIENT_SECRET` is not set. `X_REDIRECT_URI`
+	/// optionally overrides the default redirect URI. All requests, including token requests, go
+	/// through the proxy in `HTTPS_PROXY` if set.
+	///
+	/// Without the `oauth2` feature `X_BEARER_TOKEN` is required.
 	pub fn from_env() -> Self {
+		#[cfg(not(target_arch = "wasm32"))]
+		let http = match env::var("HTTPS_PROXY").or_else(|_| env::var("https_proxy")) {
+			Ok(proxy) => Client::builder()
+				.proxy(reqwest::Proxy::https(proxy).expect("HTTPS_PROXY must be a valid proxy url"))
+				.build()
+				.expect("HTTP client must be constructible; qed"),
+			Err(_) => Client::new(),
+		};
+		#[cfg(target_arch = "wasm32")]
+		let http = Client::new();
+
+		#[cfg(not(feature = "oauth2"))]
+		let bearer_token = env::var("X_BEARER_TOKEN").expect("X_BEARER_TOKEN not set");
+		#[cfg(feature = "oauth2")]
+		let Ok(bearer_token) = env::var("X_BEARER_TOKEN") else {
+			return Self::from_oauth_env(http);
+		};
+
+		Self::with_authenticator(
+			Authenticator::from_bearer_token(bearer_token).with_http_client(http),
+		)
+	}
+
+	#[cfg(feature = "oauth2")]
+	fn from_oauth_env(http: Client) -> Self {
 		let id = env::var("X_CLIENT_ID").expect("X_CLIENT_ID not set");
-		let secret = env::var("X_CLIENT_SECRET").expect("X_CLIENT_SECRET not set");
-		let authenticator = Authenticator::new(id, secret);
+		let mut authenticator = match env::var("X_CLIENT_SECRET") {
+			Ok(secret) => Authenticator::new(id, secret),
+			Err(_) => Authenticator::public(id),
+		}
+		.with_http_client(http);
+
+		if let Ok(uri) = env::var("X_REDIRECT_URI") {
+			authenticator =
+				authenticator.with_redirect_uri(uri).expect("X_REDIRECT_URI must be a valid url");
+		}
 
-		Self { authenticator, http: Client::new() }
+		Self::with_authenticator(authenticator)
 	}
 
 	/// Creates API client with provided OAuth 2.0 credentials.
+	#[cfg(feature = "oauth2")]
 	pub fn new(id: String, secret: String) -> Self {
-		let authenticator = Authenticator::new(id, secret);
+		Self::with_authenticator(Authenticator::new(id, secret))
+	}
+
+	/// Creates API client with a pre-issued bearer token, bypassing OAuth entirely.
+	pub fn with_bearer_token(bearer_token: String) -> Self {
+		Self::with_authenticator(Authenticator::from_bearer_token(bearer_token))
+	}
+
+	/// Creates API client with a pre-configured authenticator, sharing its HTTP client.
+	pub fn with_authenticator(authenticator: Authenticator) -> Self {
+		let http = authenticator.http_client().to_owned();
 
-		Self { authenticator, http: Client::new() }
+		Self::from_parts(authenticator, http)
+	}
+}
+impl<A> Api<A>
+where
+	A: AuthProvider,
+{
+	/// Creates API client with a custom auth provider, e.g. a corporate token broker or a stub.
+	pub fn with_auth_provider(authenticator: A) -> Self {
+		Self::from_parts(authenticator, Client::new())
+	}
+
+	/// Creates API client with default settings from its auth provider and HTTP client.
+	fn from_parts(authenticator: A, http: Client) -> Self {
+		Self {
+			authenticator,
+			#[cfg(feature = "oauth1")]
+			oauth1: None,
+			transport: Arc::new(http.clone()),
+			http,
+			base_url: DEFAULT_BASE_URL.into(),
+			timeout: None,
+			default_headers: HeaderMap::new(),
+			retry: RetryPolicy::default(),
+			rate_limit_behavior: RateLimitBehavior::default(),
+			rate_limits: Default::default(),
+			concurrency: Default::default(),
+			circuit_breaker: None,
+			etag_cache: None,
+			response_cache: None,
+			middleware: Vec::new(),
+			metrics: None,
+			debug_logging: false,
+			dry_run: false,
+		}
+	}
+
+	/// Returns the base URL endpoint paths are resolved against.
+	pub fn base_url(&self) -> &str {
+		&self.base_url
+	}
+
+	/// Resolves an endpoint path, e.g. `/2/tweets`, against the base URL.
+	fn url(&self, path: &str) -> String {
+		format!("{}{path}", self.base_url)
+	}
+
+	/// Returns the rate limit reported by the most recent response of the endpoint class, e.g.
+	/// `POST /2/tweets` or `GET /2/tweets/:id`.
+	pub fn last_rate_limit(&self, endpoint: &str) -> Option<RateLimit> {
+		self.rate_limits.get(endpoint)
+	}
+
+	/// Returns the remaining quota of every endpoint class seen so far, shared across clones, so
+	/// work can be scheduled without tripping limits.
+	pub fn rate_limit_status(&self) -> HashMap<String, RateLimit> {
+		self.rate_limits.status()
+	}
+
+	/// Overrides the total timeout of each request, e.g. on a cloned handle used for a single slow
+	/// call.
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+
+		self
+	}
+
+	/// Sets the policy for retrying transient failures such as 5xx responses and connection
+	/// errors.
+	pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+		self.retry = retry;
+
+		self
+	}
+
+	/// Sets how requests rejected with `429 Too Many Requests` are handled.
+	pub fn with_rate_limit_behavior(mut self, rate_limit_behavior: RateLimitBehavior) -> Self {
+		self.rate_limit_behavior = rate_limit_behavior;
+
+		self
+	}
+
+	/// Registers a middleware run around every request, after the already registered ones.
+	pub fn with_middleware<M>(mut self, middleware: M) -> Self
+	where
+		M: 'static + Middleware,
+	{
+		self.middleware.push(Arc::new(middleware));
+
+		self
+	}
+
+	/// Sets the sink receiving request counts, latencies, retries and rate limit events.
+	pub fn with_metrics_sink<M>(mut self, metrics: M) -> Self
+	where
+		M: 'static + MetricsSink,
+	{
+		self.metrics = Some(Arc::new(metrics));
+
+		self
+	}
+
+	/// Logs every request and response including bodies at trace level, with the `Authorization`
+	/// header and token fields redacted, to debug mismatches against the live API.
+	pub fn with_debug_logging(mut self, debug_logging: bool) -> Self {
+		self.debug_logging = debug_logging;
+
+		self
+	}
+
+	/// Skips mutating requests, logging what would be sent and returning a synthetic success
+	/// response instead, to develop bots against production credentials safely.
+	///
+	/// GET requests are still sent.
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+
+		self
+	}
+
+	/// Replaces the transport sending requests, e.g. with a [`MockTransport`] in tests.
+	pub fn with_transport<T>(mut self, transport: T) -> Self
+	where
+		T: 'static + HttpTransport,
+	{
+		self.transport = Arc::new(transport);
+
+		self
+	}
+
+	/// Signs every request with OAuth 1.0a user-context credentials instead of a bearer token, as
+	/// required by some legacy endpoints.
+	#[cfg(feature = "oauth1")]
+	pub fn with_oauth1(mut self, oauth1: auth::oauth1::Oauth1Credentials) -> Self {
+		self.oauth1 = Some(oauth1);
+
+		self
 	}
 
 	/// Executes HTTP requests with automatic token refresh on authentication failure.
+	///
+	/// Requests are signed with OAuth 1.0a instead if the client has been configured for it.
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(
+			name = "request",
+			skip_all,
+			fields(method, endpoint, status, retries, transaction_id)
+		)
+	)]
 	async fn execute_request<T>(
 		&self,
-		request_builder: impl Fn(&str) -> RequestBuilder,
+		options: &RequestOptions,
+		request_builder: impl Fn() -> RequestBuilder,
 	) -> Result<T>
 	where
 		T: for<'de> Deserialize<'de>,
 	{
+		let request_builder = || {
+			let request_builder = request_builder()
+				.headers(self.default_headers.clone())
+				.headers(options.headers.clone());
+
+			match options.timeout.or(self.timeout) {
+				Some(timeout) => request_builder.timeout(timeout),
+				None => request_builder,
+			}
+		};
+
+		// Recorded upfront, so dry runs and cache hits returning before any send are covered too.
+		#[cfg(feature = "tracing")]
+		if let Ok(request) = request_builder().build() {
+			let span = tracing::Span::current();
+
+			span.record("method", request.method().as_str());
+			span.record("endpoint", self.endpoint(request.url()));
+		}
+
+		if self.dry_run {
+			let request = request_builder().build()?;
+
+			if request.method() != Method::GET {
+				logging::log_dry_run(&request);
+
+				return parse_body(self.endpoint(request.url()), &dry_run_body(&request));
+			}
+		}
+
+		// Only GET responses are cached, keyed by their full URL.
+		let cache_key = (self.etag_cache.is_some() || self.response_cache.is_some())
+			.then(|| request_builder().build().ok())
+			.flatten()
+			.filter(|request| request.method() == Method::GET)
+			.map(|request| request.url().to_owned());
+
+		if let (Some(cache), Some(url)) = (&self.response_cache, &cache_key)
+			&& let Some(body) = cache.get(url.as_str())
+		{
+			return parse_body(self.endpoint(url), &body);
+		}
+
+		let cached =
+			cache_key.as_ref().zip(self.etag_cache.as_ref()).and_then(|(k, c)| c.get(k.as_str()));
+		let request_builder = || match &cached {
+			Some((etag, _)) => request_builder().header(IF_NONE_MATCH, etag),
+			None => request_builder(),
+		};
+
+		#[cfg(feature = "oauth1")]
+		if let Some(oauth1) = &self.oauth1 {
+			let resp = self
+				.send_with_retry(options, || {
+					let mut request = request_builder().build()?;
+
+					oauth1.sign(&mut request)?;
+
+					Ok(request)
+				})
+				.await?;
+			let url = resp.url().to_owned();
+			let txt = self.read_response(resp, cache_key.as_ref(), cached).await?;
+
+			return parse_body(self.endpoint(&url), &txt);
+		}
+
 		// First attempt with cached token.
-		let mut token = self.authenticator.authenticate(&self.http).await?;
+		let mut token = self.authenticator.bearer().await?;
 
 		for attempt in 0..2 {
-			let resp = request_builder(&token).send().await?;
+			let resp = self
+				.send_with_retry(options, || {
+					Ok(request_builder()
+						.header(AUTHORIZATION, format!("Bearer {token}"))
+						.build()?)
+				})
+				.await?;
 			let status = resp.status();
 
 			// If 401 and this is the first attempt, refresh token and retry.
 			if status == 401 && attempt == 0 {
-				// Force refresh and update cache since current token is invalid
-				token = self.authenticator.refresh_and_cache(&self.http).await?;
+				// Refresh since current token is invalid, unless a concurrent request already did.
+				token = self.authenticator.refresh(&token).await?;
 
 				continue;
 			}
 
-			let txt = self.handle_response(resp).await?;
+			let url = resp.url().to_owned();
+			let txt = self.read_response(resp, cache_key.as_ref(), cached).await?;
 
-			return Ok(serde_json::from_str::<T>(&txt)?);
+			return parse_body(self.endpoint(&url), &txt);
 		}
 
 		unreachable!("loop must always return within 2 attempts; qed")
 	}
 
+	/// Sends requests, retrying transient failures according to the retry policy and waiting out
+	/// rate limits according to the rate limit behavior.
+	async fn send_with_retry(
+		&self,
+		options: &RequestOptions,
+		request: impl Fn() -> Result<Request>,
+	) -> Result<Response> {
+		let retry = options.retry.as_ref().unwrap_or(&self.retry);
+		let rate_limit_behavior = options.rate_limit_behavior.unwrap_or(self.rate_limit_behavior);
+		let mut attempt = 1;
+		let mut rate_limited = 0;
+		let mut rate_limit_waited = Duration::ZERO;
+
+		loop {
+			let mut request = request()?;
+
+			for middleware in &self.middleware {
+				middleware.on_request(&mut request)?;
+			}
+
+			if self.debug_logging {
+				logging::log_request(&request);
+			}
+
+			let method = request.method().to_owned();
+			let endpoint = rate_limit::endpoint_class(&method, self.endpoint(request.url()));
+
+			#[cfg(feature = "tracing")]
+			{
+				tracing::Span::current().record("retries", attempt - 1);
+			}
+
+			if let Some(metrics) = &self.metrics {
+				metrics.on_request(&endpoint);
+			}
+
+			if let Some(circuit_breaker) = &self.circuit_breaker {
+				circuit_breaker.allow(&endpoint)?;
+			}
+
+			let permits = self.concurrency.acquire(&endpoint).await;
+			let started_at = Instant::now();
+			let resp = self.transport.execute(request).await;
+
+			drop(permits);
+
+			if let Some(circuit_breaker) = &self.circuit_breaker {
+				let failed = match &resp {
+					Ok(resp) => resp.status().is_server_error(),
+					Err(Error::Reqwest(e)) => retry.is_retryable_error(e),
+					Err(_) => false,
+				};
+
+				circuit_breaker.record(&endpoint, failed);
+			}
+
+			if let Some(metrics) = &self.metrics {
+				metrics.on_response(
+					&endpoint,
+					resp.as_ref().ok().map(Response::status),
+					started_at.elapsed(),
+				);
+			}
+
+			if let Ok(resp) = &resp {
+				#[cfg(feature = "tracing")]
+				{
+					let span = tracing::Span::current();
+
+					span.record("status", resp.status().as_u16());
+
+					if let Some(id) =
+						resp.headers().get("x-transaction-id").and_then(|id| id.to_str().ok())
+					{
+						span.record("transaction_id", id);
+					}
+				}
+
+				for middleware in &self.middleware {
+					middleware.on_response(resp)?;
+				}
+
+				if let Some(rate_limit) = RateLimit::from_headers(resp.headers()) {
+					self.rate_limits.record(endpoint.clone(), rate_limit);
+				}
+
+				if resp.status() == StatusCode::TOO_MANY_REQUESTS {
+					let wait = if rate_limited < rate_limit::MAX_RATE_LIMIT_RETRIES {
+						rate_limit_behavior.wait(resp.headers(), rate_limit_waited)
+					} else {
+						None
+					};
+
+					if let Some(metrics) = &self.metrics {
+						metrics.on_rate_limited(&endpoint, wait);
+					}
+
+					if let Some(wait) = wait {
+						warn!("⚠️ rate limited, retrying in {wait:?}");
+
+						time::sleep(wait).await;
+
+						rate_limited += 1;
+						rate_limit_waited += wait;
+
+						continue;
+					}
+				}
+			}
+
+			if !retry.is_retryable_method(&method) {
+				return resp;
+			}
+
+			let reason = match &resp {
+				Ok(resp) if retry.is_retryable_status(resp.status()) => resp.status().to_string(),
+				Err(Error::Reqwest(e)) if retry.is_retryable_error(e) => e.to_string(),
+				_ => return resp,
+			};
+
+			if attempt >= retry.max_attempts {
+				return resp;
+			}
+
+			let delay = retry.delay(attempt);
+
+			warn!("⚠️ request failed, retrying in {delay:?}: {reason}");
+
+			if let Some(metrics) = &self.metrics {
+				metrics.on_retry(&endpoint, attempt);
+			}
+
+			time::sleep(delay).await;
+
+			attempt += 1;
+		}
+	}
+
+	/// Returns the path of the URL relative to the base URL, e.g. `/2/tweets`.
+	fn endpoint<'a>(&self, url: &'a Url) -> &'a str {
+		url.as_str()
+			.strip_prefix(&self.base_url)
+			.and_then(|path| path.split(['?', '#']).next())
+			.unwrap_or(url.path())
+	}
+
+	/// Extracts response body text of cacheable requests, serving `304 Not Modified` responses
+	/// from the `ETag` cache and caching fresh ones.
+	async fn read_response(
+		&self,
+		response: Response,
+		url: Option<&Url>,
+		cached: Option<(HeaderValue, String)>,
+	) -> Result<String> {
+		let Some(url) = url else {
+			return self.handle_response(response).await;
+		};
+
+		if let Some(cache) = &self.response_cache {
+			let endpoint = rate_limit::endpoint_class(&Method::GET, self.endpoint(url));
+			let txt = self.read_etag_response(response, url, cached).await?;
+
+			cache.insert(url.to_string(), &endpoint, txt.clone());
+
+			return Ok(txt);
+		}
+
+		self.read_etag_response(response, url, cached).await
+	}
+
+	/// Extracts response body text, serving `304 Not Modified` responses from the `ETag` cache
+	/// and caching fresh ones.
+	async fn read_etag_response(
+		&self,
+		response: Response,
+		url: &Url,
+		cached: Option<(HeaderValue, String)>,
+	) -> Result<String> {
+		let Some(cache) = &self.etag_cache else {
+			return self.handle_response(response).await;
+		};
+
+		if response.status() == StatusCode::NOT_MODIFIED
+			&& let Some((_, body)) = cached
+		{
+			if self.debug_logging {
+				logging::log_response(response.url(), response.status(), &body);
+			}
+
+			return Ok(body);
+		}
+
+		let etag = response.headers().get(ETAG).cloned();
+		let txt = self.handle_response(response).await?;
+
+		if let Some(etag) = etag {
+			cache.insert(url.to_string(), etag, txt.clone());
+		}
+
+		Ok(txt)
+	}
+
 	/// Handles HTTP response status codes and extracts response body text.
 	async fn handle_response(&self, response: Response) -> Result<String> {
 		let status = response.status();
+		let url = response.url().to_owned();
+		let headers = response.headers().to_owned();
 		let txt = response.text().await?;
 
-		if status == 401 {
-			Err(Error::Unauthorized)?;
-		} else if status == 429 {
-			Err(Error::RateLimit)?;
-		} else if !status.is_success() {
-			if let Ok(e) = serde_json::from_str::<ApiError>(&txt) {
-				Err(e)?;
+		if self.debug_logging {
+			logging::log_response(&url, status, &txt);
+		}
+
+		if status.is_success() {
+			return Ok(txt);
+		}
+
+		let response = Box::new(ErrorResponse::new(status, &headers, txt));
+		let api_error = serde_json::from_str::<ApiError>(&response.body).ok().map(|mut e| {
+			if e.status == 0 {
+				e.status = status.as_u16().into();
 			}
 
-			Err(Error::any(format!("{status}: {txt}")))?;
+			Box::new(e)
+		});
+
+		if status == 401 {
+			Err(Error::Unauthorized { response: Some(response) })?
+		} else if status == 403 {
+			Err(Error::Forbidden { api_error, response })?
+		} else if status == 429 {
+			Err(Error::RateLimit { rate_limit: RateLimit::from_headers(&headers), response })?
+		} else if let Some(api_error) = api_error {
+			Err(Error::Api { api_error, response: Some(response) })?
+		} else {
+			Err(Error::Http { response })?
 		}
+	}
 
-		Ok(txt)
+	/// Sends GET requests with query parameters to API endpoints.
+	pub async fn get<Q, T>(&self, url: &str, query: &Q) -> Result<T>
+	where
+		Q: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.get_with_options(url, query, &RequestOptions::default()).await
 	}
 
-	// async fn get<T>(&self, url: &str) -> Result<T>
-	// where
-	// 	T: for<'de> Deserialize<'de>,
-	// {
-	// 	self.execute_request(|token| {
-	// 		self.http
-	// 			.get(url)
-	// 			.header(AUTHORIZATION, format!("Bearer {token}"))
-	// 			.header(CONTENT_TYPE, "application/json")
-	// 	})
-	// 	.await
-	// }
+	/// Sends GET requests with query parameters and per-request options to API endpoints.
+	pub async fn get_with_options<Q, T>(
+		&self,
+		url: &str,
+		query: &Q,
+		options: &RequestOptions,
+	) -> Result<T>
+	where
+		Q: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.execute_request(options, || self.http.get(url).query(query)).await
+	}
 
 	/// Sends POST requests with JSON body to API endpoints.
-	async fn post<B, T>(&self, url: &str, body: &B) -> Result<T>
+	pub async fn post<B, T>(&self, url: &str, body: &B) -> Result<T>
 	where
-		B: Serialize,
+		B: ?Sized + Serialize,
 		T: for<'de> Deserialize<'de>,
 	{
-		self.execute_request(|bearer| {
-			self.http
-				.post(url)
-				.header(AUTHORIZATION, format!("Bearer {bearer}"))
-				.header(CONTENT_TYPE, "application/json")
-				.json(body)
+		self.post_with_options(url, body, &RequestOptions::default()).await
+	}
+
+	/// Sends POST requests with JSON body and per-request options to API endpoints.
+	pub async fn post_with_options<B, T>(
+		&self,
+		url: &str,
+		body: &B,
+		options: &RequestOptions,
+	) -> Result<T>
+	where
+		B: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.execute_request(options, || {
+			self.http.post(url).header(CONTENT_TYPE, "application/json").json(body)
+		})
+		.await
+	}
+
+	/// Sends PUT requests with JSON body to API endpoints.
+	pub async fn put<B, T>(&self, url: &str, body: &B) -> Result<T>
+	where
+		B: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.put_with_options(url, body, &RequestOptions::default()).await
+	}
+
+	/// Sends PUT requests with JSON body and per-request options to API endpoints.
+	pub async fn put_with_options<B, T>(
+		&self,
+		url: &str,
+		body: &B,
+		options: &RequestOptions,
+	) -> Result<T>
+	where
+		B: ?Sized + Serialize,
+		T: for<'de> Deserialize<'de>,
+	{
+		self.execute_request(options, || {
+			self.http.put(url).header(CONTENT_TYPE, "application/json").json(body)
 		})
 		.await
 	}
 
-	// async fn put<B, T>(&self, url: &str, body: &B) -> Result<T>
-	// where
-	// 	B: Serialize,
-	// 	T: for<'de> Deserialize<'de>,
-	// {
-	// 	self.execute_request(|token| {
-	// 		self.http
-	// 			.put(url)
-	// 			.header(AUTHORIZATION, format!("Bearer {token}"))
-	// 			.header(CONTENT_TYPE, "application/json")
-	// 			.json(body)
-	// 	})
-	// 	.await
-	// }
-
-	// async fn delete<T>(&self, url: &str) -> Result<T>
-	// where
-	// 	T: for<'de> Deserialize<'de>,
-	// {
-	// 	self.execute_request(|token| {
-	// 		self.http
-	// 			.delete(url)
-	// 			.header(AUTHORIZATION, format!("Bearer {token}"))
-	// 			.header(CONTENT_TYPE, "application/json")
-	// 	})
-	// 	.await
-	// }
+	/// Sends DELETE requests without body to API endpoints.
+	pub async fn delete<T>(&self, url: &str) -> Result<T>
+	where
+		T: for<'de> Deserialize<'de>,
+	{
+		self.delete_with_options(url, &RequestOptions::default()).await
+	}
+
+	/// Sends DELETE requests without body and with per-request options to API endpoints.
+	pub async fn delete_with_options<T>(&self, url: &str, options: &RequestOptions) -> Result<T>
+	where
+		T: for<'de> Deserialize<'de>,
+	{
+		self.execute_request(options, || self.http.delete(url)).await
+	}
+
+	/// Sends a request to an endpoint not modeled by this crate yet, e.g. `/2/lists`, reusing
+	/// authentication, retries and error handling.
+	pub async fn request_raw(
+		&self,
+		method: Method,
+		path: &str,
+		query: &[(&str, &str)],
+		body: Option<&Value>,
+	) -> Result<Value> {
+		let url = self.url(path);
+
+		self.execute_request(&RequestOptions::default(), || {
+			let request = self.http.request(method.clone(), &url).query(query);
+
+			match body {
+				Some(body) => request.header(CONTENT_TYPE, "application/json").json(body),
+				None => request,
+			}
+		})
+		.await
+	}
+}
+
+/// Builds the synthetic success body of a request skipped in dry-run mode.
+///
+/// Deletions report `deleted`, other requests echo their JSON body with a placeholder `id`, e.g.
+/// `{"data":{"id":"0","text":"hello"}}` for a new tweet.
+fn dry_run_body(request: &Request) -> String {
+	let data = if request.method() == Method::DELETE {
+		serde_json::json!({ "deleted": true })
+	} else {
+		let mut data = request
+			.body()
+			.and_then(|body| body.as_bytes())
+			.and_then(|body| serde_json::from_slice::<Value>(body).ok())
+			.filter(Value::is_object)
+			.unwrap_or_else(|| serde_json::json!({}));
+
+		data["id"] = "0".into();
+
+		data
+	};
+
+	serde_json::json!({ "data": data }).to_string()
+}
+
+/// Deserializes a response body, treating an empty body (e.g. `204 No Content`) as `null`.
+///
+/// Failures carry the endpoint, the expected type and the start of the body for debugging.
+fn parse_body<T>(endpoint: &str, txt: &str) -> Result<T>
+where
+	T: for<'de> Deserialize<'de>,
+{
+	/// Maximum number of characters of the body kept in deserialization errors.
+	const MAX_BODY_CHARS: usize = 1024;
+
+	let txt = if txt.trim().is_empty() { "null" } else { txt };
+
+	serde_json::from_str::<T>(txt).map_err(|source| {
+		let mut body = logging::redact(txt);
+
+		if let Some((i, _)) = body.char_indices().nth(MAX_BODY_CHARS) {
+			body.truncate(i);
+			body.push_str("...");
+		}
+
+		Error::Deserialize {
+			endpoint: endpoint.into(),
+			type_name: std::any::type_name::<T>(),
+			body,
+			source,
+		}
+	})
 }
 
 /// Response wrapper that can contain either successful data or API error information.
@@ -170,22 +869,80 @@ pub enum ApiResponse<T> {
 	/// Error response containing API error details.
 	Err(ApiError),
 }
+impl<T> ApiResponse<T> {
+	/// Returns the data, or the API error as [`Error::Api`].
+	pub fn into_result(self) -> Result<T> {
+		match self {
+			Self::Ok(data) => Ok(data),
+			Self::Err(api_error) =>
+				Err(Error::Api { api_error: Box::new(api_error), response: None }),
+		}
+	}
+}
 
 #[derive(Debug, Deserialize)]
+#[serde(try_from = "RawApiError")]
 /// API error response structure containing error details from X/Twitter API.
+///
+/// Both the single problem shape `{"title":..,"detail":..}` and the `{"errors":[..]}` shape are
+/// accepted, the latter taking its summary from the first entry.
 pub struct ApiError {
 	/// Detailed description of the error that occurred.
 	pub detail: String,
-	/// HTTP status code associated with the error.
+	/// HTTP status code associated with the error, `0` if X did not include it in the body.
 	pub status: u32,
 	/// Brief title or category of the error.
 	pub title: String,
 	/// URI reference identifying the error type.
 	pub r#type: String,
+	/// Machine readable reason refining the type, e.g. `client-not-enrolled`.
+	pub reason: Option<String>,
+	/// Individual errors, e.g. one per invalid parameter or failed resource.
+	pub errors: Vec<PartialError>,
+}
+impl ApiError {
+	/// Classifies the error into one of the well-known X failures.
+	pub fn kind(&self) -> XErrorKind {
+		let detail = self
+			.errors
+			.iter()
+			.filter_map(|e| e.detail.as_deref())
+			.fold(self.detail.to_lowercase(), |detail, e| detail + "\n" + &e.to_lowercase());
+
+		if detail.contains("duplicate content") {
+			return XErrorKind::DuplicateContent;
+		}
+		if detail.contains("suspended") {
+			return XErrorKind::Suspended;
+		}
+		if self.reason.as_deref() == Some("client-not-enrolled") {
+			return XErrorKind::ClientNotEnrolled;
+		}
+
+		match self.r#type.rsplit('/').next().unwrap_or_default() {
+			"client-forbidden" => XErrorKind::ClientForbidden,
+			"not-authorized-for-resource" => XErrorKind::NotAuthorizedForResource,
+			"resource-not-found" => XErrorKind::ResourceNotFound,
+			"resource-unavailable" => XErrorKind::ResourceUnavailable,
+			"usage-capped" => XErrorKind::UsageCapped,
+			"invalid-request" => XErrorKind::InvalidRequest,
+			"unsupported-authentication" => XErrorKind::UnsupportedAuthentication,
+			_ => XErrorKind::Other,
+		}
+	}
 }
 impl Display for ApiError {
 	fn fmt(&self, f: &mut Formatter) -> FmtResult {
-		write!(f, "{} ({}): {}", self.title, self.status, self.detail)
+		if self.title.is_empty() {
+			write!(f, "{}: {}", self.status, self.detail)?;
+		} else {
+			write!(f, "{} ({}): {}", self.title, self.status, self.detail)?;
+		}
+		if self.errors.len() > 1 {
+			write!(f, " (and {} more)", self.errors.len() - 1)?;
+		}
+
+		Ok(())
 	}
 }
 impl ErrorT for ApiError {
@@ -193,3 +950,159 @@ impl ErrorT for ApiError {
 		None
 	}
 }
+
+/// Wire shape of [`ApiError`], validated to be an error rather than any JSON object.
+#[derive(Deserialize)]
+struct RawApiError {
+	detail: Option<String>,
+	status: Option<u32>,
+	title: Option<String>,
+	r#type: Option<String>,
+	reason: Option<String>,
+	#[serde(default)]
+	errors: Vec<PartialError>,
+}
+impl TryFrom<RawApiError> for ApiError {
+	type Error = &'static str;
+
+	fn try_from(raw: RawApiError) -> Result<Self, Self::Error> {
+		if raw.title.is_none() && raw.detail.is_none() && raw.errors.is_empty() {
+			return Err("neither a problem nor an errors array");
+		}
+
+		let first = raw.errors.first();
+
+		Ok(Self {
+			detail: raw.detail.or_else(|| first.and_then(|e| e.detail.clone())).unwrap_or_default(),
+			status: raw.status.unwrap_or_default(),
+			title: raw.title.or_else(|| first.map(|e| e.title.clone())).unwrap_or_default(),
+			r#type: raw.r#type.or_else(|| first.and_then(|e| e.r#type.clone())).unwrap_or_default(),
+			reason: raw.reason,
+			errors: raw.errors,
+		})
+	}
+}
+
+/// Well-known X failures, to branch on instead of matching [`ApiError::detail`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum XErrorKind {
+	/// The tweet duplicates a recent one of the user.
+	DuplicateContent,
+	/// The user or the authenticated account is suspended.
+	Suspended,
+	/// The app is not attached to a project with access to the endpoint.
+	ClientNotEnrolled,
+	/// The app is not allowed to use the endpoint, e.g. due to its access level.
+	ClientForbidden,
+	/// The user is not allowed to see the resource, e.g. a tweet of a protected account.
+	NotAuthorizedForResource,
+	/// The resource does not exist.
+	ResourceNotFound,
+	/// The resource exists but cannot be served right now.
+	ResourceUnavailable,
+	/// The monthly usage cap of the project has been reached.
+	UsageCapped,
+	/// The request is malformed, e.g. a parameter is invalid.
+	InvalidRequest,
+	/// The endpoint does not support the authentication method, e.g. app-only tokens.
+	UnsupportedAuthentication,
+	/// Any failure not classified yet.
+	Other,
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	fn api_error(json: &str) -> ApiError {
+		serde_json::from_str(json).unwrap()
+	}
+
+	#[test]
+	fn api_error_accepts_errors_array() {
+		let e = api_error(
+			r#"{"errors":[{"parameters":{"id":["abc"]},"message":"The `id` query parameter value [abc] is not valid"},{"title":"Not Found Error","detail":"Could not find tweet with id: [1].","type":"https://api.twitter.com/2/problems/resource-not-found"}],"title":"Invalid Request","detail":"One or more parameters to your request was invalid.","type":"https://api.twitter.com/2/problems/invalid-request"}"#,
+		);
+
+		assert_eq!(e.title, "Invalid Request");
+		assert_eq!(e.errors.len(), 2);
+		assert_eq!(
+			e.errors[0].detail.as_deref(),
+			Some("The `id` query parameter value [abc] is not valid")
+		);
+		assert_eq!(e.kind(), XErrorKind::InvalidRequest);
+
+		let e = api_error(
+			r#"{"errors":[{"title":"Not Found Error","detail":"Could not find tweet with id: [1].","type":"https://api.twitter.com/2/problems/resource-not-found","resource_id":"1"}]}"#,
+		);
+
+		assert_eq!(e.title, "Not Found Error");
+		assert_eq!(e.detail, "Could not find tweet with id: [1].");
+		assert_eq!(e.status, 0);
+		assert_eq!(e.kind(), XErrorKind::ResourceNotFound);
+	}
+
+	#[test]
+	fn api_error_accepts_problem() {
+		let e = api_error(
+			r#"{"title":"Unsupported Authentication","detail":"Authenticating with OAuth 2.0 Application-Only is forbidden for this endpoint.","type":"https://api.twitter.com/2/problems/unsupported-authentication","status":403}"#,
+		);
+
+		assert_eq!(e.status, 403);
+		assert!(e.errors.is_empty());
+		assert_eq!(e.kind(), XErrorKind::UnsupportedAuthentication);
+
+		let e = api_error(
+			r#"{"client_id":"1","title":"Client Forbidden","detail":"When authenticating requests to the Twitter API v2 endpoints, you must use keys and tokens from a Twitter developer App that is attached to a Project.","reason":"client-not-enrolled","type":"https://api.twitter.com/2/problems/client-forbidden","required_enrollment":"Appropriate Level of API Access","registration_url":"https://developer.twitter.com/en/docs/projects/overview"}"#,
+		);
+
+		assert_eq!(e.reason.as_deref(), Some("client-not-enrolled"));
+		assert_eq!(e.kind(), XErrorKind::ClientNotEnrolled);
+
+		let e = api_error(
+			r#"{"detail":"You are not allowed to create a Tweet with duplicate content.","type":"about:blank","title":"Forbidden","status":403}"#,
+		);
+
+		assert_eq!(e.kind(), XErrorKind::DuplicateContent);
+	}
+
+	#[test]
+	fn api_error_falls_back_on_unknown_type() {
+		let e = api_error(
+			r#"{"title":"Something New","detail":"Not classified yet.","type":"https://api.twitter.com/2/problems/something-new"}"#,
+		);
+
+		assert_eq!(e.kind(), XErrorKind::Other);
+		assert_eq!(api_error(r#"{"detail":"Too Many Requests"}"#).kind(), XErrorKind::Other);
+	}
+
+	#[test]
+	fn api_error_rejects_other_objects() {
+		assert!(serde_json::from_str::<ApiError>(r#"{"data":{"id":"1","text":"a"}}"#).is_err());
+		assert!(serde_json::from_str::<ApiError>(r#"{"errors":[]}"#).is_err());
+	}
+
+	#[test]
+	fn parse_body_truncates_redacted_body() {
+		let txt = format!(r#"{{"access_token":"secret","text":"{}"}}"#, "é".repeat(2000));
+		let Err(Error::Deserialize { endpoint, body, .. }) = parse_body::<u32>("/2/tweets", &txt)
+		else {
+			panic!("expected a deserialization error");
+		};
+
+		assert_eq!(endpoint, "/2/tweets");
+		assert!(!body.contains("secret"));
+		assert!(body.ends_with("..."));
+		assert_eq!(body.chars().count(), 1024 + 3);
+	}
+
+	#[test]
+	fn parse_body_treats_empty_body_as_null() {
+		assert_eq!(parse_body::<Option<u32>>("/2/tweets/1", " ").unwrap(), None);
+		assert!(matches!(
+			parse_body::<u32>("/2/tweets/1", "oops"),
+			Err(Error::Deserialize { body, .. }) if body == "oops"
+		));
+	}
+}
@@ -0,0 +1,38 @@
+//! X/Twitter web link helpers.
+
+// crates.io
+use url::Url;
+
+/// Base URL of the X web app.
+pub(crate) const WEB_BASE: &str = "https://x.com";
+
+/// Returns the path segments of an X/Twitter link, accepting links without a scheme.
+pub(crate) fn path_segments(link: &str) -> Option<(Url, Vec<String>)> {
+	let link = link.trim();
+	let url = if link.contains("://") {
+		Url::parse(link)
+	} else {
+		Url::parse(&format!("https://{link}"))
+	}
+	.ok()?;
+	let host = url.host_str()?.to_ascii_lowercase();
+	let host = host.strip_prefix("www.").or_else(|| host.strip_prefix("mobile.")).unwrap_or(&host);
+
+	if !matches!(host, "x.com" | "twitter.com") {
+		return None;
+	}
+
+	let segments = url.path_segments()?.filter(|s| !s.is_empty()).map(Into::into).collect();
+
+	Some((url, segments))
+}
+
+/// Returns whether the string is a numeric ID.
+pub(crate) fn is_id(s: &str) -> bool {
+	!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Returns whether the string is a valid handle, without the leading `@`.
+pub(crate) fn is_username(s: &str) -> bool {
+	(1..=15).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
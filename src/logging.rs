@@ -0,0 +1,114 @@
+//! Request and response logging with secret redaction.
+
+// crates.io
+use reqwest::{Request, StatusCode, Url, header::AUTHORIZATION};
+use serde_json::Value;
+
+/// Placeholder replacing redacted values.
+const REDACTED: &str = "<redacted>";
+
+/// Logs the method, URL, headers and body of the request at trace level.
+pub(crate) fn log_request(request: &Request) {
+	let headers = request
+		.headers()
+		.iter()
+		.map(|(name, value)| {
+			let value =
+				if name == AUTHORIZATION { REDACTED } else { value.to_str().unwrap_or("<binary>") };
+
+			format!("{name}: {value}")
+		})
+		.collect::<Vec<_>>()
+		.join(", ");
+	let body = request
+		.body()
+		.and_then(|body| body.as_bytes())
+		.map(|body| redact(&String::from_utf8_lossy(body)))
+		.unwrap_or_default();
+
+	trace!("➡️ {} {} [{headers}] {body}", request.method(), request.url());
+}
+
+/// Logs the method, URL and body of a request skipped in dry-run mode at info level.
+pub(crate) fn log_dry_run(request: &Request) {
+	let body = request
+		.body()
+		.and_then(|body| body.as_bytes())
+		.map(|body| redact(&String::from_utf8_lossy(body)))
+		.unwrap_or_default();
+
+	info!("🧪 dry run, not sending {} {} {body}", request.method(), request.url());
+}
+
+/// Logs the status and body of the response at trace level.
+pub(crate) fn log_response(url: &Url, status: StatusCode, body: &str) {
+	trace!("⬅️ {status} {url} {}", redact(body));
+}
+
+/// Redacts the values of token and secret fields in JSON bodies.
+pub(crate) fn redact(body: &str) -> String {
+	fn redact_value(value: &mut Value) {
+		match value {
+			Value::Object(map) =>
+				for (key, value) in map {
+					if is_secret(key) {
+						*value = Value::String(REDACTED.into());
+					} else {
+						redact_value(value);
+					}
+				},
+			Value::Array(values) => values.iter_mut().for_each(redact_value),
+			_ => (),
+		}
+	}
+
+	match serde_json::from_str::<Value>(body) {
+		Ok(mut value) => {
+			redact_value(&mut value);
+
+			value.to_string()
+		},
+		Err(_) => body.into(),
+	}
+}
+
+/// Returns whether the field holds a secret, keeping pagination tokens such as `next_token`
+/// visible.
+fn is_secret(key: &str) -> bool {
+	let key = key.to_ascii_lowercase();
+
+	key.contains("secret")
+		|| key.contains("password")
+		|| matches!(
+			key.as_str(),
+			"token" | "access_token" | "refresh_token" | "id_token" | "oauth_token"
+		)
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	#[test]
+	fn redact_hides_secrets_only() {
+		let body = redact(
+			r#"{"access_token":"a","token_type":"bearer","refresh_token":"r","meta":{"next_token":"n"},"data":[{"client_secret":"s","Password":"p","id":"1"}]}"#,
+		);
+		let body = serde_json::from_str::<Value>(&body).unwrap();
+
+		assert_eq!(body["access_token"], REDACTED);
+		assert_eq!(body["refresh_token"], REDACTED);
+		assert_eq!(body["data"][0]["client_secret"], REDACTED);
+		assert_eq!(body["data"][0]["Password"], REDACTED);
+		assert_eq!(body["token_type"], "bearer");
+		assert_eq!(body["meta"]["next_token"], "n");
+		assert_eq!(body["data"][0]["id"], "1");
+	}
+
+	#[test]
+	fn redact_keeps_non_json_bodies() {
+		assert_eq!(redact("Too Many Requests"), "Too Many Requests");
+		assert_eq!(redact(""), "");
+	}
+}
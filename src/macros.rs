@@ -0,0 +1,31 @@
+//! Logging macros forwarding to `tracing` if the `logging` feature is enabled.
+//!
+//! Without it the arguments are still type checked, so builds stay warning-free, but nothing is
+//! logged.
+
+#[cfg(feature = "logging")]
+macro_rules! trace {
+	($($arg:tt)*) => { tracing::trace!($($arg)*) };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! trace {
+	($($arg:tt)*) => { { let _ = format_args!($($arg)*); } };
+}
+
+#[cfg(feature = "logging")]
+macro_rules! info {
+	($($arg:tt)*) => { tracing::info!($($arg)*) };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! info {
+	($($arg:tt)*) => { { let _ = format_args!($($arg)*); } };
+}
+
+#[cfg(feature = "logging")]
+macro_rules! warn {
+	($($arg:tt)*) => { tracing::warn!($($arg)*) };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! warn {
+	($($arg:tt)*) => { { let _ = format_args!($($arg)*); } };
+}
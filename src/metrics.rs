@@ -0,0 +1,24 @@
+//! Metrics hooks.
+
+// std
+use std::{fmt::Debug, time::Duration};
+// crates.io
+use reqwest::StatusCode;
+
+/// Sink receiving request metrics, e.g. to export them to Prometheus or StatsD.
+///
+/// Endpoints are reported by class, e.g. `GET /2/tweets/:id`, to keep label cardinality bounded.
+pub trait MetricsSink: Debug + Send + Sync {
+	/// Called before each attempt of a request is sent.
+	fn on_request(&self, _endpoint: &str) {}
+
+	/// Called once an attempt completed, with `None` as status if it failed without a response.
+	fn on_response(&self, _endpoint: &str, _status: Option<StatusCode>, _latency: Duration) {}
+
+	/// Called before a failed attempt is retried.
+	fn on_retry(&self, _endpoint: &str, _attempt: u32) {}
+
+	/// Called when a request is rejected with `429 Too Many Requests`, with the time waited before
+	/// retrying if the request is going to be retried.
+	fn on_rate_limited(&self, _endpoint: &str, _wait: Option<Duration>) {}
+}
@@ -0,0 +1,25 @@
+//! Request middleware.
+
+// std
+use std::fmt::Debug;
+// crates.io
+use reqwest::{Request, Response};
+// self
+use crate::prelude::*;
+
+/// Interceptor run around every HTTP request sent by [`Api`], e.g. to inject custom headers, audit
+/// requests or inject faults for chaos testing.
+///
+/// Middleware run in registration order, after the request has been authenticated and before each
+/// attempt is sent, including retries.
+pub trait Middleware: Debug + Send + Sync {
+	/// Mutates the request before it is sent, aborting it on error.
+	fn on_request(&self, _request: &mut Request) -> Result<()> {
+		Ok(())
+	}
+
+	/// Inspects the response before it is handled, failing the request on error.
+	fn on_response(&self, _response: &Response) -> Result<()> {
+		Ok(())
+	}
+}
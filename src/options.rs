@@ -0,0 +1,64 @@
+//! Per-request option overrides.
+
+// std
+use std::time::Duration;
+// crates.io
+use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName};
+// self
+use crate::{RateLimitBehavior, RetryPolicy};
+
+/// Options overriding the client configuration for a single call, e.g. to disable retries on a
+/// latency sensitive path or to wait out rate limits in a backfill job.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+	/// Total timeout of each attempt.
+	pub timeout: Option<Duration>,
+	/// Policy for retrying transient failures.
+	pub retry: Option<RetryPolicy>,
+	/// How requests rejected with `429 Too Many Requests` are handled.
+	pub rate_limit_behavior: Option<RateLimitBehavior>,
+	/// Extra headers sent with the request.
+	pub headers: HeaderMap,
+}
+impl RequestOptions {
+	/// Creates options falling back to the client configuration.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Overrides the total timeout of each attempt.
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+
+		self
+	}
+
+	/// Overrides the policy for retrying transient failures.
+	pub fn retry(mut self, retry: RetryPolicy) -> Self {
+		self.retry = Some(retry);
+
+		self
+	}
+
+	/// Disables retries of transient failures.
+	pub fn no_retry(self) -> Self {
+		self.retry(RetryPolicy::none())
+	}
+
+	/// Overrides how requests rejected with `429 Too Many Requests` are handled.
+	pub fn rate_limit_behavior(mut self, rate_limit_behavior: RateLimitBehavior) -> Self {
+		self.rate_limit_behavior = Some(rate_limit_behavior);
+
+		self
+	}
+
+	/// Adds an extra header sent with the request.
+	pub fn header<K>(mut self, name: K, value: HeaderValue) -> Self
+	where
+		K: IntoHeaderName,
+	{
+		self.headers.insert(name, value);
+
+		self
+	}
+}
@@ -0,0 +1,302 @@
+//! Pagination of list endpoints.
+
+// std
+use std::collections::VecDeque;
+// crates.io
+use futures_util::{Stream, stream};
+// self
+use crate::{ApiResponse, Envelope, Fields, prelude::*};
+
+/// Page fetcher of a [`Paginator`], called with the token of the page to fetch.
+#[cfg(not(target_arch = "wasm32"))]
+type Fetch<'a, T> = Box<
+	dyn 'a + Send + FnMut(Option<String>) -> BoxFuture<'a, Result<ApiResponse<Envelope<Vec<T>>>>>,
+>;
+/// Page fetcher of a [`Paginator`], called with the token of the page to fetch.
+#[cfg(target_arch = "wasm32")]
+type Fetch<'a, T> =
+	Box<dyn 'a + FnMut(Option<String>) -> BoxFuture<'a, Result<ApiResponse<Envelope<Vec<T>>>>>>;
+
+/// Page size and position of list endpoints using `pagination_token`, e.g. quote tweets.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Pagination {
+	/// Number of objects per page, within the range allowed by the endpoint.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_results: Option<u32>,
+	/// Token of the page to fetch, `meta.next_token` of the previous page.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pagination_token: Option<String>,
+}
+impl Pagination {
+	/// Creates parameters fetching the first page with the default size.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the number of objects per page.
+	pub fn max_results(mut self, max_results: u32) -> Self {
+		self.max_results = Some(max_results);
+
+		self
+	}
+
+	/// Fetches the page of the token.
+	pub fn pagination_token(mut self, pagination_token: String) -> Self {
+		self.pagination_token = Some(pagination_token);
+
+		self
+	}
+}
+
+/// Turns any paginated endpoint into a stream, following `meta.next_token` until the last page
+/// or the limit is reached.
+///
+/// The fetcher is called with `None` for the first page and the token of the next page after
+/// that, which it passes as `pagination_token` or `next_token` depending on the endpoint. API
+/// errors returned with a successful status end the stream as [`Error::Api`].
+///
+/// Long-running exports can checkpoint the [`PaginatorState`] yielded by
+/// [`Paginator::pages_with_state`] and continue from it after a restart with
+/// [`Paginator::resume`]. The state records the request set through [`Paginator::request`], so
+/// resuming it with a different one fails instead of mixing up results.
+pub struct Paginator<'a, T> {
+	fetch: Fetch<'a, T>,
+	limit: Option<usize>,
+	state: PaginatorState,
+	failed: bool,
+}
+impl<'a, T> Paginator<'a, T>
+where
+	T: 'a,
+{
+	/// Creates a paginator fetching pages with the fetcher.
+	pub fn new<F, Fut>(mut fetch: F) -> Self
+	where
+		F: 'a + MaybeSend + FnMut(Option<String>) -> Fut,
+		Fut: 'a + MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<T>>>>>,
+	{
+		Self {
+			fetch: Box::new(move |token| Box::pin(fetch(token))),
+			limit: None,
+			state: Default::default(),
+			failed: false,
+		}
+	}
+
+	/// Stops once the number of items is reached, instead of at the last page.
+	pub fn limit(mut self, limit: usize) -> Self {
+		self.limit = Some(limit);
+
+		self
+	}
+
+	/// Identifies the request the fetcher sends, e.g. its path and parameters, which the
+	/// paginators of this crate set themselves.
+	pub fn request<S>(mut self, request: S) -> Self
+	where
+		S: Into<String>,
+	{
+		self.state.request = Some(request.into());
+
+		self
+	}
+
+	/// Continues from a checkpointed state instead of the first page.
+	///
+	/// Fails if the state was taken from a paginator for a different request.
+	pub fn resume(mut self, state: PaginatorState) -> Result<Self> {
+		if let (Some(request), Some(resumed)) = (&self.state.request, &state.request)
+			&& request != resumed
+		{
+			Err(Error::any(format!("paginator state of `{resumed}` cannot resume `{request}`")))?;
+		}
+
+		let request = self.state.request.take().or(state.request);
+
+		self.state = PaginatorState { request, ..state };
+
+		Ok(self)
+	}
+
+	/// Returns the stream of pages, e.g. to read their includes.
+	///
+	/// The limit stops fetching further pages but does not truncate the last one.
+	pub fn pages(self) -> impl 'a + Stream<Item = Result<Envelope<Vec<T>>>> {
+		stream::unfold(self, |mut paginator| async move {
+			let page = paginator.next_page().await?;
+
+			Some((page, paginator))
+		})
+	}
+
+	/// Returns the stream of pages, each with the state to resume from once it is processed.
+	pub fn pages_with_state(
+		self,
+	) -> impl 'a + Stream<Item = Result<(Envelope<Vec<T>>, PaginatorState)>> {
+		stream::unfold(self, |mut paginator| async move {
+			let page = paginator.next_page().await?.map(|page| (page, paginator.state.clone()));
+
+			Some((page, paginator))
+		})
+	}
+
+	/// Returns the stream of items across pages.
+	pub fn items(self) -> impl 'a + Stream<Item = Result<T>> {
+		let limit = self.limit;
+		let yielded = self.state.fetched;
+
+		stream::unfold(
+			(self, VecDeque::new(), yielded),
+			move |(mut paginator, mut items, mut yielded)| async move {
+				if limit.is_some_and(|limit| yielded >= limit) {
+					return None;
+				}
+
+				while items.is_empty() {
+					match paginator.next_page().await? {
+						Ok(page) => items.extend(page.data.unwrap_or_default()),
+						Err(e) => return Some((Err(e), (paginator, items, yielded))),
+					}
+				}
+
+				yielded += 1;
+
+				Some((Ok(items.pop_front()?), (paginator, items, yielded)))
+			},
+		)
+	}
+
+	/// Fetches the next page, `None` after the last page, the limit or an error.
+	async fn next_page(&mut self) -> Option<Result<Envelope<Vec<T>>>> {
+		let state = &mut self.state;
+
+		if self.failed || state.done || self.limit.is_some_and(|limit| state.fetched >= limit) {
+			return None;
+		}
+
+		let page =
+			match (self.fetch)(state.next_token.clone()).await.and_then(ApiResponse::into_result) {
+				Ok(page) => page,
+				Err(e) => {
+					// Keep the token so the failed page is fetched again on resume.
+					self.failed = true;
+
+					return Some(Err(e));
+				},
+			};
+
+		state.fetched += page.data.as_ref().map_or(0, Vec::len);
+		state.next_token = page.meta.next_token.clone();
+		state.done = state.next_token.is_none();
+
+		Some(Ok(page))
+	}
+}
+
+/// Position of a [`Paginator`], serializable to checkpoint long-running exports.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PaginatorState {
+	/// Request paged through, its path and parameters, if known.
+	#[serde(default)]
+	pub request: Option<String>,
+	/// Token of the next page, `None` before the first page and after the last one.
+	pub next_token: Option<String>,
+	/// Number of items fetched so far, counted towards the limit.
+	pub fetched: usize,
+	/// Whether the last page was fetched.
+	pub done: bool,
+}
+
+/// Returns the request paged through by a paginator, the path with the parameters and fields
+/// sent for every page.
+pub(crate) fn request_key<P>(path: &str, params: &P, fields: &Fields) -> String
+where
+	P: Serialize,
+{
+	let query = serde_json::to_string(&FieldsQuery { params, fields })
+		.expect("query parameters must be serializable; qed");
+
+	format!("GET {path} {query}")
+}
+
+/// Query of endpoint parameters merged with fields and expansions.
+#[derive(Serialize)]
+pub(crate) struct FieldsQuery<'a, P> {
+	#[serde(flatten)]
+	pub(crate) params: &'a P,
+	#[serde(flatten)]
+	pub(crate) fields: &'a Fields,
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+	// crates.io
+	use futures_util::StreamExt;
+	use reqwest::{Method, StatusCode};
+	// self
+	use super::*;
+	use crate::{
+		MockTransport,
+		timelines::{ApiTimeline, TimelineParams},
+	};
+
+	fn api() -> (Api, MockTransport) {
+		let transport = MockTransport::new().with_response(
+			Method::GET,
+			"/2/users/1/tweets",
+			StatusCode::OK,
+			r#"{"data":[{"id":"3","text":"c"}],"meta":{"result_count":1,"next_token":"next"}}"#,
+		);
+
+		(Api::with_bearer_token("token".into()).with_transport(transport.clone()), transport)
+	}
+
+	#[tokio::test]
+	async fn resume_continues_from_next_token() {
+		let (api, transport) = api();
+		let fields = Fields::new();
+		let params = TimelineParams::new().max_results(10);
+		let pages = api.user_tweets_stream("1", &params, &fields).pages_with_state();
+		let (_, state) = std::pin::pin!(pages).next().await.unwrap().unwrap();
+
+		assert_eq!(state.next_token.as_deref(), Some("next"));
+		assert_eq!(state.fetched, 1);
+
+		let state = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+		let items = api
+			.user_tweets_stream("1", &params, &fields)
+			.limit(2)
+			.resume(state)
+			.unwrap()
+			.items()
+			.collect::<Vec<_>>()
+			.await;
+		let requests = transport.requests();
+
+		assert_eq!(items.len(), 1);
+		assert_eq!(requests.len(), 2);
+		assert!(requests[1].url().query().unwrap().contains("pagination_token=next"));
+	}
+
+	#[test]
+	fn resume_rejects_state_of_another_request() {
+		let (api, _) = api();
+		let fields = Fields::new();
+		let state = PaginatorState {
+			next_token: Some("next".into()),
+			..api
+				.user_tweets_stream("1", &TimelineParams::new().max_results(10), &fields)
+				.state
+				.clone()
+		};
+
+		assert!(
+			api.user_tweets_stream("1", &TimelineParams::new().max_results(20), &fields)
+				.resume(state.clone())
+				.is_err()
+		);
+		assert!(
+			api.user_tweets_stream("2", &TimelineParams::new(), &fields).resume(state).is_err()
+		);
+	}
+}
@@ -0,0 +1,158 @@
+//! Rate limit tracking.
+
+// std
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+// crates.io
+use reqwest::{Method, header::HeaderMap};
+// self
+use crate::time::unix_now;
+
+/// Most retries of a single request after `429 Too Many Requests`.
+pub(crate) const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// How requests rejected with `429 Too Many Requests` are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitBehavior {
+	/// Fail immediately with [`Error::RateLimit`](crate::error::Error::RateLimit).
+	#[default]
+	Error,
+	/// Sleep until the window resets and retry, failing instead once the waits of a request would
+	/// add up to more than `max_wait` or after 3 rate limited retries, e.g. for batch backfill
+	/// jobs.
+	WaitAndRetry {
+		/// Longest total time to wait for windows to reset across the retries of a request.
+		max_wait: Duration,
+	},
+}
+impl RateLimitBehavior {
+	/// Returns how long to wait before retrying a rate limited request which already waited for
+	/// `waited`, if at all.
+	pub(crate) fn wait(&self, headers: &HeaderMap, waited: Duration) -> Option<Duration> {
+		let Self::WaitAndRetry { max_wait } = self else {
+			return None;
+		};
+		let rate_limit = RateLimit::from_headers(headers)?;
+		// Wait one more second as the reset timestamp is truncated.
+		let wait = rate_limit.reset_in() + Duration::from_secs(1);
+
+		(waited + wait <= *max_wait).then_some(wait)
+	}
+}
+
+/// Rate limit window reported by the `x-rate-limit-*` response headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+	/// Maximum number of requests allowed in the window.
+	pub limit: u32,
+	/// Number of requests left in the current window.
+	pub remaining: u32,
+	/// Unix timestamp in seconds at which the window resets.
+	pub reset: u64,
+}
+impl RateLimit {
+	/// Parses the rate limit headers, returning `None` if any of them is missing or malformed.
+	pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+		fn header<T>(headers: &HeaderMap, name: &str) -> Option<T>
+		where
+			T: std::str::FromStr,
+		{
+			headers.get(name)?.to_str().ok()?.trim().parse().ok()
+		}
+
+		Some(Self {
+			limit: header(headers, "x-rate-limit-limit")?,
+			remaining: header(headers, "x-rate-limit-remaining")?,
+			reset: header(headers, "x-rate-limit-reset")?,
+		})
+	}
+
+	/// Returns the window as of now, with the full quota available again once it has reset.
+	pub fn current(self) -> Self {
+		if self.reset <= unix_now() { Self { remaining: self.limit, ..self } } else { self }
+	}
+
+	/// Returns how long until the window resets, zero if it already has.
+	pub fn reset_in(&self) -> Duration {
+		Duration::from_secs(self.reset.saturating_sub(unix_now()))
+	}
+
+	/// Returns whether no requests are left in the current window.
+	pub fn is_exhausted(&self) -> bool {
+		self.current().remaining == 0
+	}
+}
+
+/// Rate limit budgets per endpoint class, shared across clones of [`Api`](crate::Api).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RateLimitTracker(Arc<RwLock<HashMap<String, RateLimit>>>);
+impl RateLimitTracker {
+	/// Records the rate limit reported by a response of the endpoint class.
+	pub(crate) fn record(&self, endpoint: String, rate_limit: RateLimit) {
+		self.0.write().expect("lock must not be poisoned; qed").insert(endpoint, rate_limit);
+	}
+
+	/// Returns the current budget of the endpoint class.
+	pub(crate) fn get(&self, endpoint: &str) -> Option<RateLimit> {
+		self.0.read().expect("lock must not be poisoned; qed").get(endpoint).map(|r| r.current())
+	}
+
+	/// Returns the current budgets of all endpoint classes seen so far.
+	pub(crate) fn status(&self) -> HashMap<String, RateLimit> {
+		self.0
+			.read()
+			.expect("lock must not be poisoned; qed")
+			.iter()
+			.map(|(endpoint, r)| (endpoint.to_owned(), r.current()))
+			.collect()
+	}
+}
+
+/// Returns the endpoint class of a request, e.g. `GET /2/tweets/:id`, as X rate limits requests
+/// per method and route rather than per resource.
+pub(crate) fn endpoint_class(method: &Method, path: &str) -> String {
+	let mut class = format!("{method} ");
+	let mut previous = "";
+
+	for (i, segment) in path.trim_matches('/').split('/').enumerate() {
+		class.push('/');
+		class.push_str(match segment {
+			// Keep the API version, e.g. `2`.
+			_ if i == 0 => segment,
+			_ if previous == "username" => ":username",
+			_ if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) => ":id",
+			_ => segment,
+		});
+
+		previous = segment;
+	}
+
+	class
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	#[test]
+	fn endpoint_class_replaces_ids_and_usernames() {
+		assert_eq!(endpoint_class(&Method::GET, "/2/tweets/20"), "GET /2/tweets/:id");
+		assert_eq!(
+			endpoint_class(&Method::DELETE, "/2/users/12/likes/20/"),
+			"DELETE /2/users/:id/likes/:id"
+		);
+		assert_eq!(
+			endpoint_class(&Method::GET, "/2/users/by/username/jack"),
+			"GET /2/users/by/username/:username"
+		);
+		assert_eq!(
+			endpoint_class(&Method::GET, "/2/tweets/search/recent"),
+			"GET /2/tweets/search/recent"
+		);
+		assert_eq!(endpoint_class(&Method::POST, "/2/tweets"), "POST /2/tweets");
+	}
+}
@@ -0,0 +1,48 @@
+//! Raw JSON preservation.
+
+// std
+use std::ops::Deref;
+// crates.io
+use serde::{Deserializer, de::Error as DeError};
+use serde_json::Value;
+// self
+use crate::prelude::*;
+
+/// Typed response kept together with its raw JSON, giving access to fields the typed model
+/// doesn't cover yet.
+///
+/// Opt in by requesting e.g. `RawPayload<Envelope<TweetData>>` instead of the plain model.
+#[derive(Clone, Debug)]
+pub struct RawPayload<T> {
+	/// Response deserialized into the typed model.
+	pub parsed: T,
+	/// Response as received.
+	pub raw: Value,
+}
+impl<T> RawPayload<T> {
+	/// Returns the typed model, discarding the raw JSON.
+	pub fn into_parsed(self) -> T {
+		self.parsed
+	}
+}
+impl<T> Deref for RawPayload<T> {
+	type Target = T;
+
+	fn deref(&self) -> &Self::Target {
+		&self.parsed
+	}
+}
+impl<'de, T> Deserialize<'de> for RawPayload<T>
+where
+	T: for<'a> Deserialize<'a>,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let raw = Value::deserialize(deserializer)?;
+		let parsed = T::deserialize(&raw).map_err(D::Error::custom)?;
+
+		Ok(Self { parsed, raw })
+	}
+}
@@ -0,0 +1,178 @@
+//! VCR-style recording and replaying of HTTP interactions.
+
+// std
+use std::{
+	collections::BTreeMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+// crates.io
+use reqwest::{
+	Request, Response, StatusCode,
+	header::{HeaderMap, HeaderName, HeaderValue},
+};
+// self
+use crate::{HttpTransport, logging, prelude::*, transport};
+
+/// Transport recording real interactions to a cassette file, or replaying them from it, so tests
+/// run deterministically against captured production payloads.
+///
+/// Request headers are never recorded, token fields in bodies are redacted and only rate limit and
+/// transaction ID response headers are kept. Interactions are matched by method, path and query
+/// and each is replayed once, in recording order.
+#[derive(Clone, Debug)]
+pub struct ReplayTransport {
+	mode: Mode,
+	path: PathBuf,
+	interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+impl ReplayTransport {
+	/// Records interactions sent through the inner transport to the cassette, replacing it.
+	pub fn record<P, T>(path: P, inner: T) -> Self
+	where
+		P: Into<PathBuf>,
+		T: 'static + HttpTransport,
+	{
+		Self {
+			mode: Mode::Record(Arc::new(inner)),
+			path: path.into(),
+			interactions: Default::default(),
+		}
+	}
+
+	/// Replays interactions from the cassette without touching the network.
+	pub fn replay<P>(path: P) -> Result<Self>
+	where
+		P: Into<PathBuf>,
+	{
+		let path = path.into();
+		let cassette = serde_json::from_slice::<Cassette>(&fs::read(&path)?)?;
+
+		Ok(Self {
+			mode: Mode::Replay,
+			path,
+			interactions: Arc::new(Mutex::new(cassette.interactions)),
+		})
+	}
+
+	/// Returns the path of the cassette.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	async fn record_interaction(
+		&self,
+		inner: &dyn HttpTransport,
+		request: Request,
+	) -> Result<Response> {
+		let method = request.method().to_string();
+		let request_body = request
+			.body()
+			.and_then(|b| b.as_bytes())
+			.map(|b| logging::redact(&String::from_utf8_lossy(b)));
+		let response = inner.execute(request).await?;
+		let url = response.url().to_owned();
+		let status = response.status();
+		let headers = kept_headers(response.headers());
+		let body = response.text().await?;
+		let interaction = Interaction {
+			method,
+			url: path_and_query(&url),
+			request_body,
+			status: status.as_u16(),
+			headers: headers
+				.iter()
+				.filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_owned())))
+				.collect(),
+			body: logging::redact(&body),
+		};
+		let cassette = {
+			let mut interactions =
+				self.interactions.lock().expect("lock must not be poisoned; qed");
+
+			interactions.push(interaction);
+
+			serde_json::to_vec_pretty(&Cassette { interactions: interactions.clone() })?
+		};
+
+		fs::write(&self.path, cassette)?;
+
+		transport::build_response(url, status, headers, body)
+	}
+
+	fn replay_interaction(&self, request: Request) -> Result<Response> {
+		let method = request.method().as_str();
+		let url = path_and_query(request.url());
+		let interaction = {
+			let mut interactions =
+				self.interactions.lock().expect("lock must not be poisoned; qed");
+			let i = interactions
+				.iter()
+				.position(|i| i.method == method && i.url == url)
+				.ok_or_else(|| Error::any(format!("no recorded interaction for {method} {url}")))?;
+
+			interactions.remove(i)
+		};
+		let status = StatusCode::from_u16(interaction.status)
+			.map_err(|e| Error::any(format!("invalid recorded status: {e}")))?;
+		let headers = interaction
+			.headers
+			.iter()
+			.filter_map(|(k, v)| {
+				Some((HeaderName::try_from(k).ok()?, HeaderValue::from_str(v).ok()?))
+			})
+			.collect();
+
+		transport::build_response(request.url().to_owned(), status, headers, interaction.body)
+	}
+}
+impl HttpTransport for ReplayTransport {
+	fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+		Box::pin(async move {
+			match &self.mode {
+				Mode::Record(inner) => self.record_interaction(inner.as_ref(), request).await,
+				Mode::Replay => self.replay_interaction(request),
+			}
+		})
+	}
+}
+
+#[derive(Clone, Debug)]
+enum Mode {
+	Record(Arc<dyn HttpTransport>),
+	Replay,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cassette {
+	interactions: Vec<Interaction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Interaction {
+	method: String,
+	url: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	request_body: Option<String>,
+	status: u16,
+	#[serde(default)]
+	headers: BTreeMap<String, String>,
+	body: String,
+}
+
+/// Returns the URL without scheme and host, so cassettes work with any base URL.
+fn path_and_query(url: &reqwest::Url) -> String {
+	match url.query() {
+		Some(query) => format!("{}?{query}", url.path()),
+		None => url.path().into(),
+	}
+}
+
+fn kept_headers(headers: &HeaderMap) -> HeaderMap {
+	headers
+		.iter()
+		.filter(|(k, _)| k.as_str().starts_with("x-rate-limit-") || *k == "x-transaction-id")
+		.map(|(k, v)| (k.to_owned(), v.to_owned()))
+		.collect()
+}
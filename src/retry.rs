@@ -0,0 +1,103 @@
+//! Retry policy for data API requests.
+
+// std
+use std::{
+	hash::{BuildHasher, RandomState},
+	time::Duration,
+};
+// crates.io
+use reqwest::{Method, StatusCode};
+
+/// Retry policy for transient data API failures, independent of token endpoint retries.
+///
+/// Connection errors, timeouts and the configured server error statuses are considered transient.
+/// Only idempotent requests, e.g. `GET` and `DELETE`, are retried unless
+/// [`RetryPolicy::retry_non_idempotent`] is set, as a `POST` whose response got lost may have been
+/// applied already and would be applied twice, e.g. publishing a duplicate tweet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+	/// Maximum number of attempts including the first one.
+	pub max_attempts: u32,
+	/// Delay before the first retry, doubled after every further attempt.
+	pub base_delay: Duration,
+	/// Upper bound of the delay between attempts.
+	pub max_delay: Duration,
+	/// Whether to randomize each delay between half and the full value, so clients recovering
+	/// from the same outage don't retry in lockstep.
+	pub jitter: bool,
+	/// Response statuses considered transient.
+	pub retry_statuses: Vec<u16>,
+	/// Whether to also retry non-idempotent requests, e.g. `POST`, at the risk of applying them
+	/// twice.
+	pub retry_non_idempotent: bool,
+}
+impl RetryPolicy {
+	/// Creates a policy which never retries.
+	pub fn none() -> Self {
+		Self { max_attempts: 1, ..Default::default() }
+	}
+
+	/// Returns whether requests with the method may be retried.
+	pub fn is_retryable_method(&self, method: &Method) -> bool {
+		self.retry_non_idempotent || method.is_idempotent()
+	}
+
+	/// Returns whether responses with the status should be retried.
+	pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+		self.retry_statuses.contains(&status.as_u16())
+	}
+
+	/// Returns whether the transport error should be retried.
+	pub fn is_retryable_error(&self, e: &reqwest::Error) -> bool {
+		#[cfg(not(target_arch = "wasm32"))]
+		if e.is_connect() {
+			return true;
+		}
+
+		e.is_timeout() || e.is_request()
+	}
+
+	pub(crate) fn delay(&self, attempt: u32) -> Duration {
+		let delay = self.base_delay.saturating_mul(1 << (attempt - 1).min(16)).min(self.max_delay);
+
+		if self.jitter {
+			// Scale by a random factor in `[0.5, 1.0)`.
+			let random = RandomState::new().hash_one(attempt) as f64 / u64::MAX as f64;
+
+			delay.mul_f64(0.5 + random / 2.)
+		} else {
+			delay
+		}
+	}
+}
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 3,
+			base_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(8),
+			jitter: true,
+			retry_statuses: vec![500, 502, 503, 504],
+			retry_non_idempotent: false,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	#[test]
+	fn only_idempotent_methods_are_retried_by_default() {
+		let policy = RetryPolicy::default();
+
+		assert!(policy.is_retryable_method(&Method::GET));
+		assert!(policy.is_retryable_method(&Method::DELETE));
+		assert!(!policy.is_retryable_method(&Method::POST));
+
+		let policy = RetryPolicy { retry_non_idempotent: true, ..Default::default() };
+
+		assert!(policy.is_retryable_method(&Method::POST));
+	}
+}
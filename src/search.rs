@@ -0,0 +1,371 @@
+//! X/Twitter V2 Search API
+
+mod query;
+pub use query::*;
+
+// std
+use std::time::Duration;
+// crates.io
+use serde::{Deserialize, Serialize};
+// self
+use crate::{
+	ApiResponse, Envelope, Fields, FieldsQuery, RateLimitBehavior, RequestOptions, Timestamp,
+	auth::{AuthProvider, Scopes},
+	prelude::*,
+	time::{self, Instant},
+	tweets::TweetData,
+};
+
+/// Scopes required to search tweets.
+const SEARCH_SCOPES: &[&str] = &[Scopes::TWEET_READ, Scopes::USERS_READ];
+/// Endpoint class of full-archive search.
+const SEARCH_ALL_ENDPOINT: &str = "GET /2/tweets/search/all";
+/// Minimum interval between full-archive search requests, which X limits to 1 per second.
+const SEARCH_ALL_INTERVAL: Duration = Duration::from_secs(1);
+/// Longest wait for a rate limit window to reset while draining, a full 15 minute window.
+const DRAIN_MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+
+/// Trait for searching tweets on X/Twitter API.
+pub trait ApiSearch {
+	/// Searches tweets of the last 7 days matching the query, one page at a time.
+	fn search_recent(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Searches tweets of the last 7 days matching the query, one page at a time, overriding the
+	/// client configuration for this call.
+	fn search_recent_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Searches tweets since 2006 matching the query, one page at a time.
+	///
+	/// Requires Pro or Enterprise access and is limited to 1 request per second.
+	fn search_all(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Searches tweets since 2006 matching the query, one page at a time, overriding the client
+	/// configuration for this call.
+	fn search_all_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Pages through every full-archive search result, handing each page to the callback.
+	///
+	/// Requests are paced to 1 per second and wait for the rate limit window to reset once it is
+	/// exhausted. On failure, resume from the `next_token` of the last reported progress.
+	fn drain_search_all<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		on_page: F,
+	) -> impl MaybeSend + Future<Output = Result<DrainProgress>>
+	where
+		F: MaybeSend + FnMut(Envelope<Vec<TweetData>>, &DrainProgress);
+
+	/// Pages through every full-archive search result, overriding the client configuration for
+	/// this call.
+	///
+	/// Rate limited requests wait for the window to reset unless the options set another
+	/// behavior.
+	fn drain_search_all_with_options<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+		on_page: F,
+	) -> impl MaybeSend + Future<Output = Result<DrainProgress>>
+	where
+		F: MaybeSend + FnMut(Envelope<Vec<TweetData>>, &DrainProgress);
+}
+/// Implementation of tweet search functionality for the main API client.
+impl<A> ApiSearch for Api<A>
+where
+	A: AuthProvider,
+{
+	async fn search_recent(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.search_recent_with_options(query, params, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+	async fn search_recent_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.authenticator.ensure_scopes(SEARCH_SCOPES)?;
+
+		let query = SearchQuery { query, params };
+
+		self.get_with_options(
+			&self.url("/2/tweets/search/recent"),
+			&FieldsQuery { params: &query, fields },
+			options,
+		)
+		.await
+	}
+
+	async fn search_all(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.search_all_with_options(query, params, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+	async fn search_all_with_options(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.authenticator.ensure_scopes(SEARCH_SCOPES)?;
+
+		let query = SearchQuery { query, params };
+
+		self.get_with_options(
+			&self.url("/2/tweets/search/all"),
+			&FieldsQuery { params: &query, fields },
+			options,
+		)
+		.await
+	}
+
+	async fn drain_search_all<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		on_page: F,
+	) -> Result<DrainProgress>
+	where
+		F: MaybeSend + FnMut(Envelope<Vec<TweetData>>, &DrainProgress),
+	{
+		self.drain_search_all_with_options(
+			query,
+			params,
+			fields,
+			&RequestOptions::default(),
+			on_page,
+		)
+		.await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+	async fn drain_search_all_with_options<F>(
+		&self,
+		query: &str,
+		params: &SearchParams,
+		fields: &Fields,
+		options: &RequestOptions,
+		mut on_page: F,
+	) -> Result<DrainProgress>
+	where
+		F: MaybeSend + FnMut(Envelope<Vec<TweetData>>, &DrainProgress),
+	{
+		let mut options = options.clone();
+
+		if options.rate_limit_behavior.is_none() {
+			options.rate_limit_behavior =
+				Some(RateLimitBehavior::WaitAndRetry { max_wait: DRAIN_MAX_WAIT });
+		}
+
+		let mut params = params.clone();
+		let mut progress = DrainProgress::default();
+		let mut last_request = None::<Instant>;
+
+		loop {
+			if let Some(wait) =
+				last_request.and_then(|last| SEARCH_ALL_INTERVAL.checked_sub(last.elapsed()))
+			{
+				time::sleep(wait).await;
+			}
+			if let Some(rate_limit) =
+				self.last_rate_limit(SEARCH_ALL_ENDPOINT).filter(|r| r.is_exhausted())
+			{
+				// Wait one more second as the reset timestamp is truncated.
+				let wait = rate_limit.reset_in() + Duration::from_secs(1);
+
+				info!("rate limit exhausted after {} pages, waiting {wait:?}", progress.pages);
+
+				time::sleep(wait).await;
+			}
+
+			last_request = Some(Instant::now());
+
+			let page = self.search_all_with_options(query, &params, fields, &options).await?;
+			let page = page.into_result()?;
+
+			progress.pages += 1;
+			progress.tweets += page.data.as_ref().map_or(0, Vec::len);
+			progress.next_token = page.meta.next_token.clone();
+
+			if page.meta.oldest_id.is_some() {
+				progress.oldest_id = page.meta.oldest_id.clone();
+			}
+			if progress.newest_id.is_none() {
+				progress.newest_id = page.meta.newest_id.clone();
+			}
+
+			on_page(page, &progress);
+
+			match &progress.next_token {
+				Some(next_token) => params.next_token = Some(next_token.to_owned()),
+				None => return Ok(progress),
+			}
+		}
+	}
+}
+
+/// Progress of draining a full-archive search.
+#[derive(Clone, Debug, Default)]
+pub struct DrainProgress {
+	/// Number of pages fetched so far.
+	pub pages: usize,
+	/// Number of tweets fetched so far.
+	pub tweets: usize,
+	/// ID of the newest tweet fetched, from the first page.
+	pub newest_id: Option<String>,
+	/// ID of the oldest tweet fetched so far.
+	pub oldest_id: Option<String>,
+	/// Token of the next page, `None` once drained.
+	pub next_token: Option<String>,
+}
+
+/// Time range, ID range and pagination of a recent or full-archive search.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SearchParams {
+	/// Number of tweets per page, 10 to 100 for recent search and up to 500 for full-archive
+	/// search.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_results: Option<u32>,
+	/// Oldest time of the tweets, inclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub start_time: Option<Timestamp>,
+	/// Newest time of the tweets, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub end_time: Option<Timestamp>,
+	/// Only tweets newer than the tweet, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub since_id: Option<String>,
+	/// Only tweets older than the tweet, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub until_id: Option<String>,
+	/// Order of the tweets, newest first if unset.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sort_order: Option<SortOrder>,
+	/// Token of the page to fetch, `meta.next_token` of the previous page.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub next_token: Option<String>,
+}
+impl SearchParams {
+	/// Creates parameters fetching the first page of the default time range.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the number of tweets per page.
+	pub fn max_results(mut self, max_results: u32) -> Self {
+		self.max_results = Some(max_results);
+
+		self
+	}
+
+	/// Only returns tweets posted at or after the time.
+	pub fn start_time(mut self, start_time: Timestamp) -> Self {
+		self.start_time = Some(start_time);
+
+		self
+	}
+
+	/// Only returns tweets posted at or after the time.
+	#[cfg(feature = "chrono")]
+	pub fn start_time_utc(self, start_time: chrono::DateTime<chrono::Utc>) -> Self {
+		self.start_time(time::format_timestamp(start_time))
+	}
+
+	/// Only returns tweets posted before the time.
+	pub fn end_time(mut self, end_time: Timestamp) -> Self {
+		self.end_time = Some(end_time);
+
+		self
+	}
+
+	/// Only returns tweets posted before the time.
+	#[cfg(feature = "chrono")]
+	pub fn end_time_utc(self, end_time: chrono::DateTime<chrono::Utc>) -> Self {
+		self.end_time(time::format_timestamp(end_time))
+	}
+
+	/// Only returns tweets newer than the tweet, e.g. the newest one of the last poll.
+	pub fn since_id(mut self, since_id: String) -> Self {
+		self.since_id = Some(since_id);
+
+		self
+	}
+
+	/// Only returns tweets older than the tweet.
+	pub fn until_id(mut self, until_id: String) -> Self {
+		self.until_id = Some(until_id);
+
+		self
+	}
+
+	/// Sets the order of the tweets.
+	pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
+		self.sort_order = Some(sort_order);
+
+		self
+	}
+
+	/// Fetches the page of the token.
+	pub fn next_token(mut self, next_token: String) -> Self {
+		self.next_token = Some(next_token);
+
+		self
+	}
+}
+
+/// Order of search results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+	/// Newest tweets first, e.g. for incremental polling with `since_id`.
+	Recency,
+	/// Most relevant tweets first.
+	Relevancy,
+}
+
+#[derive(Serialize)]
+struct SearchQuery<'a> {
+	query: &'a str,
+	#[serde(flatten)]
+	params: &'a SearchParams,
+}
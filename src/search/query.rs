@@ -0,0 +1,359 @@
+//! Search query builder composing X search operators.
+
+// std
+use std::fmt::{Display, Formatter, Result as FmtResult};
+// self
+use crate::{links, prelude::*};
+
+/// Search query composed of operators, all of which must match unless combined with
+/// [`Query::or`].
+///
+/// Terms are quoted and escaped as needed, e.g. `Query::new().keyword("new york").from("@x")`
+/// builds `"new york" from:x`. Invalid terms, e.g. an empty keyword or a username with spaces,
+/// make [`Query::build`] fail.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Query {
+	operators: Vec<String>,
+	invalid: Option<String>,
+}
+impl Query {
+	/// Creates an empty query.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Matches the keyword, quoted if it contains whitespace or reserved characters or would
+	/// otherwise be read as an operator, e.g. `-x`, `#x` or `OR`.
+	pub fn keyword(self, keyword: &str) -> Self {
+		if keyword.trim().is_empty() {
+			return self.reject("search term must not be empty");
+		}
+
+		if keyword.starts_with(['-', '#', '@', '$'])
+			|| matches!(keyword, "OR" | "AND")
+			|| keyword.chars().any(|c| c.is_whitespace() || "\"():".contains(c))
+		{
+			self.phrase(keyword)
+		} else {
+			self.push(keyword.to_owned())
+		}
+	}
+
+	/// Matches the exact phrase.
+	pub fn phrase(self, phrase: &str) -> Self {
+		if phrase.trim().is_empty() {
+			return self.reject("search phrase must not be empty");
+		}
+
+		self.push(format!("\"{}\"", phrase.replace('\\', "\\\\").replace('"', "\\\"")))
+	}
+
+	/// Matches the hashtag, with or without the leading `#`.
+	pub fn hashtag(self, hashtag: &str) -> Self {
+		let hashtag = hashtag.strip_prefix('#').unwrap_or(hashtag);
+
+		if hashtag.is_empty() || !hashtag.chars().all(|c| c.is_alphanumeric() || c == '_') {
+			return self.reject(format!("invalid hashtag: {hashtag}"));
+		}
+
+		self.push(format!("#{hashtag}"))
+	}
+
+	/// Matches tweets mentioning the user, with or without the leading `@`.
+	pub fn mention(self, username: &str) -> Self {
+		let username = username.strip_prefix('@').unwrap_or(username);
+
+		if !links::is_username(username) {
+			return self.reject(format!("invalid username: {username}"));
+		}
+
+		self.push(format!("@{username}"))
+	}
+
+	/// Matches tweets posted by the user, by username or ID.
+	pub fn from(self, user: &str) -> Self {
+		self.user_operator("from", user)
+	}
+
+	/// Matches tweets replying to the user, by username or ID.
+	pub fn to(self, user: &str) -> Self {
+		self.user_operator("to", user)
+	}
+
+	/// Matches tweets of the thread started by the tweet.
+	pub fn conversation_id(self, tweet_id: &str) -> Self {
+		if !links::is_id(tweet_id) {
+			return self.reject(format!("invalid tweet id: {tweet_id}"));
+		}
+
+		self.push(format!("conversation_id:{tweet_id}"))
+	}
+
+	/// Matches tweets X classified as the BCP 47 language, e.g. `en`.
+	pub fn lang(self, lang: &str) -> Self {
+		let mut subtags = lang.split('-');
+		let primary = subtags.next().unwrap_or_default();
+		let valid = (2..=3).contains(&primary.len())
+			&& primary.bytes().all(|b| b.is_ascii_alphabetic())
+			&& subtags.all(|s| {
+				(1..=8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric())
+			});
+
+		if !valid {
+			return self.reject(format!("invalid language code: {lang}"));
+		}
+
+		self.push(format!("lang:{lang}"))
+	}
+
+	/// Matches tweets containing the attachment or entity.
+	pub fn has(self, has: Has) -> Self {
+		self.push(format!("has:{}", has.as_str()))
+	}
+
+	/// Matches tweets of the kind.
+	pub fn is(self, is: Is) -> Self {
+		self.push(format!("is:{}", is.as_str()))
+	}
+
+	/// Matches tweets the other query matches too, grouped if it has several operators.
+	pub fn and(self, query: Query) -> Self {
+		let this = self.merge_invalid(&query);
+
+		match query.grouped() {
+			Some(query) => this.push(query),
+			None => this,
+		}
+	}
+
+	/// Matches tweets the other query does not match, e.g. `-is:retweet`.
+	pub fn not(self, query: Query) -> Self {
+		let this = self.merge_invalid(&query);
+
+		match query.grouped() {
+			Some(query) => this.push(format!("-{query}")),
+			None => this,
+		}
+	}
+
+	/// Matches tweets either this query or the other query matches.
+	pub fn or(self, query: Query) -> Self {
+		let invalid = self.invalid.clone().or_else(|| query.invalid.clone());
+		let operators = match (self.grouped(), query.grouped()) {
+			(Some(this), Some(other)) => vec![format!("({this} OR {other})")],
+			(Some(_), None) => self.operators,
+			(None, _) => query.operators,
+		};
+
+		Self { operators, invalid }
+	}
+
+	/// Returns the query, failing if it has invalid terms, is empty or is longer than the access
+	/// level allows.
+	pub fn build(&self, access_level: AccessLevel) -> Result<String> {
+		if let Some(invalid) = &self.invalid {
+			Err(Error::any(invalid.to_owned()))?;
+		}
+
+		let query = self.to_string();
+		let len = query.chars().count();
+		let max_len = access_level.max_query_len();
+
+		if len == 0 {
+			Err(Error::any("query must not be empty"))?;
+		}
+		if len > max_len {
+			Err(Error::any(format!(
+				"query is {len} characters long, over the {max_len} allowed for {access_level:?} access"
+			)))?;
+		}
+
+		Ok(query)
+	}
+
+	fn user_operator(self, operator: &str, user: &str) -> Self {
+		let user = user.strip_prefix('@').unwrap_or(user);
+
+		if !links::is_username(user) && !links::is_id(user) {
+			return self.reject(format!("invalid username or user id: {user}"));
+		}
+
+		self.push(format!("{operator}:{user}"))
+	}
+
+	fn push(mut self, operator: String) -> Self {
+		self.operators.push(operator);
+
+		self
+	}
+
+	/// Records the first invalid term, reported by [`Query::build`].
+	fn reject<S>(mut self, reason: S) -> Self
+	where
+		S: Into<String>,
+	{
+		self.invalid.get_or_insert_with(|| reason.into());
+
+		self
+	}
+
+	fn merge_invalid(mut self, query: &Query) -> Self {
+		if self.invalid.is_none() {
+			self.invalid.clone_from(&query.invalid);
+		}
+
+		self
+	}
+
+	fn grouped(&self) -> Option<String> {
+		match self.operators.as_slice() {
+			[] => None,
+			[operator] => Some(operator.to_owned()),
+			_ => Some(format!("({self})")),
+		}
+	}
+}
+impl Display for Query {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		f.write_str(&self.operators.join(" "))
+	}
+}
+
+/// X API access level, determining the maximum query length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessLevel {
+	/// Self-serve access with 512 character queries.
+	Basic,
+	/// Self-serve access with 1024 character queries.
+	Pro,
+	/// Enterprise access with 4096 character queries.
+	Enterprise,
+}
+impl AccessLevel {
+	/// Returns the maximum query length in characters.
+	pub fn max_query_len(&self) -> usize {
+		match self {
+			Self::Basic => 512,
+			Self::Pro => 1_024,
+			Self::Enterprise => 4_096,
+		}
+	}
+}
+
+/// Attachment or entity matched by the `has:` operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Has {
+	/// `has:cashtags`
+	Cashtags,
+	/// `has:geo`
+	Geo,
+	/// `has:hashtags`
+	Hashtags,
+	/// `has:images`
+	Images,
+	/// `has:links`
+	Links,
+	/// `has:media`
+	Media,
+	/// `has:mentions`
+	Mentions,
+	/// `has:videos`
+	Videos,
+}
+impl Has {
+	/// Returns the name sent to the API.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Cashtags => "cashtags",
+			Self::Geo => "geo",
+			Self::Hashtags => "hashtags",
+			Self::Images => "images",
+			Self::Links => "links",
+			Self::Media => "media",
+			Self::Mentions => "mentions",
+			Self::Videos => "videos",
+		}
+	}
+}
+
+/// Kind of tweet matched by the `is:` operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Is {
+	/// `is:nullcast`, i.e. promoted-only tweets.
+	Nullcast,
+	/// `is:quote`
+	Quote,
+	/// `is:reply`
+	Reply,
+	/// `is:retweet`
+	Retweet,
+	/// `is:verified`, i.e. tweets of verified authors.
+	Verified,
+}
+impl Is {
+	/// Returns the name sent to the API.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Nullcast => "nullcast",
+			Self::Quote => "quote",
+			Self::Reply => "reply",
+			Self::Retweet => "retweet",
+			Self::Verified => "verified",
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	#[test]
+	fn keywords_read_as_operators_are_quoted() {
+		let query = Query::new().keyword("OR").keyword("-x").keyword("a:b").keyword("rust");
+
+		assert_eq!(query.build(AccessLevel::Basic).unwrap(), r#""OR" "-x" "a:b" rust"#);
+	}
+
+	#[test]
+	fn phrase_escapes_quotes_and_backslashes() {
+		let query = Query::new().phrase(r#"say "hi" \o/"#);
+
+		assert_eq!(query.build(AccessLevel::Basic).unwrap(), r#""say \"hi\" \\o/""#);
+	}
+
+	#[test]
+	fn nested_queries_are_grouped() {
+		let query = Query::new()
+			.keyword("rust")
+			.and(Query::new().from("a").or(Query::new().from("b").lang("en")))
+			.not(Query::new().is(Is::Retweet).has(Has::Links));
+
+		assert_eq!(
+			query.build(AccessLevel::Basic).unwrap(),
+			"rust (from:a OR (from:b lang:en)) -(is:retweet has:links)"
+		);
+	}
+
+	#[test]
+	fn invalid_terms_fail_to_build() {
+		for query in [
+			Query::new().keyword(""),
+			Query::new().from("a b"),
+			Query::new().lang("en OR x"),
+			Query::new().conversation_id("1a"),
+			Query::new().keyword("ok").or(Query::new().hashtag("#")),
+		] {
+			assert!(query.build(AccessLevel::Basic).is_err(), "{query:?}");
+		}
+	}
+
+	#[test]
+	fn query_length_is_limited_by_access_level() {
+		let max = AccessLevel::Basic.max_query_len();
+
+		assert!(Query::new().keyword(&"a".repeat(max)).build(AccessLevel::Basic).is_ok());
+		assert!(Query::new().keyword(&"a".repeat(max + 1)).build(AccessLevel::Basic).is_err());
+		assert!(Query::new().keyword(&"a".repeat(max + 1)).build(AccessLevel::Pro).is_ok());
+	}
+}
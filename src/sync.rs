@@ -0,0 +1,145 @@
+//! Incremental sync checkpoints.
+
+// std
+use std::{
+	collections::HashMap,
+	fmt::Debug,
+	fs,
+	io::ErrorKind,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+// self
+use crate::{
+	Meta, auth::write_atomic, prelude::*, search::SearchParams, timelines::TimelineParams,
+};
+
+/// Persistence backend of [`SyncCursor`] checkpoints, keyed by endpoint and user.
+pub trait CheckpointStore: Debug + Send + Sync {
+	/// Loads the newest seen ID of the key, returning `None` if nothing has been stored yet.
+	fn load(&self, key: &str) -> Result<Option<String>>;
+
+	/// Persists the newest seen ID of the key, replacing the previous one.
+	fn save(&self, key: &str, id: &str) -> Result<()>;
+}
+
+/// Non-persistent checkpoint store keeping IDs in memory for the lifetime of the process.
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+	ids: Mutex<HashMap<String, String>>,
+}
+impl CheckpointStore for MemoryCheckpointStore {
+	fn load(&self, key: &str) -> Result<Option<String>> {
+		Ok(self.ids.lock().expect("lock must not be poisoned; qed").get(key).cloned())
+	}
+
+	fn save(&self, key: &str, id: &str) -> Result<()> {
+		self.ids.lock().expect("lock must not be poisoned; qed").insert(key.into(), id.into());
+
+		Ok(())
+	}
+}
+
+/// Checkpoint store persisting IDs of every key as a JSON object on the local filesystem.
+///
+/// Writes are atomic.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+	path: PathBuf,
+	lock: Mutex<()>,
+}
+impl FileCheckpointStore {
+	/// Creates a file store backed by the given path, which is created on first save.
+	pub fn new<P>(path: P) -> Self
+	where
+		P: Into<PathBuf>,
+	{
+		Self { path: path.into(), lock: Mutex::new(()) }
+	}
+
+	/// Returns the path of the backing file.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	fn read(&self) -> Result<HashMap<String, String>> {
+		match fs::read(&self.path) {
+			Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(HashMap::new()),
+			Err(e) => Err(e)?,
+		}
+	}
+}
+impl CheckpointStore for FileCheckpointStore {
+	fn load(&self, key: &str) -> Result<Option<String>> {
+		let _lock = self.lock.lock().expect("lock must not be poisoned; qed");
+
+		Ok(self.read()?.remove(key))
+	}
+
+	fn save(&self, key: &str, id: &str) -> Result<()> {
+		let _lock = self.lock.lock().expect("lock must not be poisoned; qed");
+		let mut ids = self.read()?;
+
+		ids.insert(key.into(), id.into());
+
+		write_atomic(&self.path, &serde_json::to_vec_pretty(&ids)?)
+	}
+}
+
+/// Newest seen ID of an endpoint for a user, fed back as `since_id` so recurring jobs only
+/// receive new content.
+///
+/// Advance the cursor only once the fetched content has been processed, so a crash in between
+/// fetches it again instead of skipping it.
+#[derive(Clone, Debug)]
+pub struct SyncCursor {
+	store: Arc<dyn CheckpointStore>,
+	key: String,
+}
+impl SyncCursor {
+	/// Creates a cursor of the endpoint, e.g. `mentions`, for the user or query.
+	pub fn new<S>(store: S, endpoint: &str, user: &str) -> Self
+	where
+		S: 'static + CheckpointStore,
+	{
+		Self::with_shared_store(Arc::new(store), endpoint, user)
+	}
+
+	/// Creates a cursor sharing the store with other cursors.
+	pub fn with_shared_store(store: Arc<dyn CheckpointStore>, endpoint: &str, user: &str) -> Self {
+		Self { store, key: format!("{endpoint}:{user}") }
+	}
+
+	/// Returns the newest seen ID, `None` before the first sync.
+	pub fn since_id(&self) -> Result<Option<String>> {
+		self.store.load(&self.key)
+	}
+
+	/// Returns the timeline parameters resuming after the newest seen ID.
+	pub fn timeline_params(&self, params: TimelineParams) -> Result<TimelineParams> {
+		Ok(TimelineParams { since_id: self.since_id()?.or(params.since_id), ..params })
+	}
+
+	/// Returns the search parameters resuming after the newest seen ID.
+	pub fn search_params(&self, params: SearchParams) -> Result<SearchParams> {
+		Ok(SearchParams { since_id: self.since_id()?.or(params.since_id), ..params })
+	}
+
+	/// Records the ID as seen, unless a newer one already is.
+	pub fn advance(&self, id: &str) -> Result<()> {
+		match self.since_id()? {
+			// IDs are time-ordered snowflakes, so shorter ones are older.
+			Some(since_id) if (since_id.len(), &*since_id) >= (id.len(), id) => Ok(()),
+			_ => self.store.save(&self.key, id),
+		}
+	}
+
+	/// Records the newest ID of the first page of a sync as seen.
+	pub fn commit(&self, meta: &Meta) -> Result<()> {
+		match &meta.newest_id {
+			Some(newest_id) => self.advance(newest_id),
+			None => Ok(()),
+		}
+	}
+}
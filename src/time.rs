@@ -0,0 +1,71 @@
+//! Clock and timer shims working on native and WASM targets.
+
+// std
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+// crates.io
+#[cfg(target_arch = "wasm32")] pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// ISO 8601 timestamp as sent and returned by X, e.g. `2024-01-01T00:00:00.000Z`.
+///
+/// Kept as a string regardless of features, the `chrono` feature adds `*_utc` accessors and
+/// builders working with `chrono::DateTime<Utc>` instead.
+pub type Timestamp = String;
+
+/// Waits until the duration has elapsed.
+pub(crate) async fn sleep(duration: Duration) {
+	#[cfg(not(target_arch = "wasm32"))]
+	tokio::time::sleep(duration).await;
+	#[cfg(target_arch = "wasm32")]
+	gloo_timers::future::sleep(duration).await;
+}
+
+/// Parses a timestamp returned by X, `None` if it is malformed.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_timestamp(timestamp: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+	chrono::DateTime::parse_from_rfc3339(timestamp).ok().map(|t| t.to_utc())
+}
+
+/// Formats a timestamp the way X accepts it in query parameters.
+#[cfg(feature = "chrono")]
+pub(crate) fn format_timestamp(timestamp: chrono::DateTime<chrono::Utc>) -> Timestamp {
+	timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Returns the current Unix timestamp in seconds.
+pub(crate) fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+	// crates.io
+	use chrono::{TimeZone, Utc};
+	// self
+	use super::*;
+
+	#[test]
+	fn parse_timestamp_accepts_x_format() {
+		let expected = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+		assert_eq!(parse_timestamp("2024-01-02T03:04:05.000Z"), Some(expected));
+		assert_eq!(parse_timestamp("2024-01-02T03:04:05Z"), Some(expected));
+		assert_eq!(parse_timestamp("2024-01-02T05:04:05+02:00"), Some(expected));
+		assert_eq!(parse_timestamp("2024-01-02 03:04:05"), None);
+		assert_eq!(parse_timestamp(""), None);
+	}
+
+	#[test]
+	fn format_timestamp_round_trips() {
+		let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+		let formatted = format_timestamp(timestamp);
+
+		assert_eq!(formatted, "2024-01-02T03:04:05Z");
+		assert_eq!(parse_timestamp(&formatted), Some(timestamp));
+		assert_eq!(
+			parse_timestamp("2024-01-02T03:04:05.000Z").map(format_timestamp).as_deref(),
+			Some("2024-01-02T03:04:05Z")
+		);
+	}
+}
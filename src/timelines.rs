@@ -0,0 +1,503 @@
+//! X/Twitter V2 Timelines API
+
+// std
+use std::{
+	collections::{BTreeSet, VecDeque},
+	fmt::{Display, Formatter, Result as FmtResult},
+	str::FromStr,
+	time::Duration,
+};
+// crates.io
+use futures_util::{Stream, stream};
+use serde::Serialize;
+// self
+use crate::{
+	ApiResponse, Envelope, Fields, FieldsQuery, Paginator, RateLimitBehavior, RequestOptions,
+	Timestamp,
+	auth::{AuthProvider, Scopes},
+	fields::{comma_separated, from_comma_separated},
+	pagination,
+	prelude::*,
+	time,
+	tweets::TweetData,
+};
+
+/// Scopes required to read timelines.
+const TIMELINE_SCOPES: &[&str] = &[Scopes::TWEET_READ, Scopes::USERS_READ];
+/// Longest wait for a rate limit window to reset while watching, a full 15 minute window.
+const WATCH_MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+/// Number of recently yielded tweet IDs remembered to drop duplicates.
+const WATCH_SEEN_CAPACITY: usize = 1_000;
+
+/// Trait for reading user timelines on X/Twitter API.
+pub trait ApiTimeline {
+	/// Lists the tweets posted by the user, newest first, one page at a time.
+	fn user_tweets(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Lists the tweets posted by the user, newest first, one page at a time, overriding the
+	/// client configuration for this call.
+	fn user_tweets_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Streams the tweets posted by the user, newest first, fetching pages lazily.
+	///
+	/// The page size is taken from `max_results` and the total can be capped with
+	/// [`Paginator::limit`].
+	fn user_tweets_stream<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		fields: &'a Fields,
+	) -> Paginator<'a, TweetData>;
+
+	/// Lists the tweets mentioning the user, newest first, one page at a time.
+	fn user_mentions(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Lists the tweets mentioning the user, newest first, one page at a time, overriding the
+	/// client configuration for this call.
+	fn user_mentions_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Streams the tweets mentioning the user, newest first, fetching pages lazily.
+	///
+	/// The page size is taken from `max_results` and the total can be capped with
+	/// [`Paginator::limit`].
+	fn user_mentions_stream<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		fields: &'a Fields,
+	) -> Paginator<'a, TweetData>;
+
+	/// Polls the mentions of the user every interval, yielding each new mention once, oldest
+	/// first.
+	///
+	/// Polling resumes after `since_id` of the parameters, or starts from now if unset. Rate
+	/// limited polls wait for the window to reset, and after other transient errors polling backs
+	/// off exponentially; the stream ends on errors that retrying cannot fix.
+	fn watch_mentions<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		interval: Duration,
+		fields: &'a Fields,
+	) -> impl 'a + MaybeSend + Stream<Item = Result<TweetData>>;
+}
+/// Implementation of timeline functionality for the main API client.
+impl<A> ApiTimeline for Api<A>
+where
+	A: AuthProvider,
+{
+	async fn user_tweets(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.user_tweets_with_options(user_id, params, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(user_id = %user_id)))]
+	async fn user_tweets_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.authenticator.ensure_scopes(TIMELINE_SCOPES)?;
+
+		let url = self.url(&format!("/2/users/{}/tweets", urlencoding::encode(user_id)));
+
+		self.get_with_options(&url, &FieldsQuery { params, fields }, options).await
+	}
+
+	fn user_tweets_stream<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		fields: &'a Fields,
+	) -> Paginator<'a, TweetData> {
+		let params = TimelineParams { pagination_token: None, ..params.clone() };
+		let request =
+			pagination::request_key(&format!("/2/users/{user_id}/tweets"), &params, fields);
+
+		Paginator::new(move |token| {
+			let params = TimelineParams { pagination_token: token, ..params.clone() };
+
+			async move { self.user_tweets(user_id, &params, fields).await }
+		})
+		.request(request)
+	}
+
+	async fn user_mentions(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.user_mentions_with_options(user_id, params, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(user_id = %user_id)))]
+	async fn user_mentions_with_options(
+		&self,
+		user_id: &str,
+		params: &TimelineParams,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.authenticator.ensure_scopes(TIMELINE_SCOPES)?;
+
+		let url = self.url(&format!("/2/users/{}/mentions", urlencoding::encode(user_id)));
+		// The mentions endpoint rejects `exclude`.
+		let params = &TimelineParams { exclude: BTreeSet::new(), ..params.clone() };
+
+		self.get_with_options(&url, &FieldsQuery { params, fields }, options).await
+	}
+
+	fn user_mentions_stream<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		fields: &'a Fields,
+	) -> Paginator<'a, TweetData> {
+		let params =
+			TimelineParams { exclude: BTreeSet::new(), pagination_token: None, ..params.clone() };
+		let request =
+			pagination::request_key(&format!("/2/users/{user_id}/mentions"), &params, fields);
+
+		Paginator::new(move |token| {
+			let params = TimelineParams { pagination_token: token, ..params.clone() };
+
+			async move { self.user_mentions(user_id, &params, fields).await }
+		})
+		.request(request)
+	}
+
+	fn watch_mentions<'a>(
+		&'a self,
+		user_id: &'a str,
+		params: &TimelineParams,
+		interval: Duration,
+		fields: &'a Fields,
+	) -> impl 'a + MaybeSend + Stream<Item = Result<TweetData>> {
+		let state = WatchState {
+			params: TimelineParams { pagination_token: None, ..params.clone() },
+			options: RequestOptions::new()
+				.rate_limit_behavior(RateLimitBehavior::WaitAndRetry { max_wait: WATCH_MAX_WAIT }),
+			pending: VecDeque::new(),
+			seen: VecDeque::new(),
+			polled: false,
+			failures: 0,
+			backoff: None,
+			done: false,
+		};
+
+		stream::unfold(state, move |mut state| async move {
+			loop {
+				if let Some(tweet) = state.pending.pop_front() {
+					return Some((Ok(tweet), state));
+				}
+				if state.done {
+					return None;
+				}
+				if let Some(backoff) = state.backoff.take() {
+					time::sleep(backoff).await;
+				} else if state.polled {
+					time::sleep(interval).await;
+				}
+
+				match state.poll(self, user_id, fields).await {
+					Ok(()) => state.failures = 0,
+					Err(e) => {
+						state.done = !e.is_retryable();
+						state.failures += 1;
+						state.backoff = Some(watch_backoff(&e, interval, state.failures));
+
+						return Some((Err(e), state));
+					},
+				}
+			}
+		})
+	}
+}
+
+/// Returns how long to wait before polling again after the failed poll, until the rate limit
+/// window resets if known and with exponential backoff from the interval otherwise.
+fn watch_backoff(e: &Error, interval: Duration, failures: u32) -> Duration {
+	let backoff = match e.retry_after() {
+		// Wait one more second as the reset timestamp is truncated.
+		Some(retry_after) => retry_after + Duration::from_secs(1),
+		None => interval.max(Duration::from_secs(1)).saturating_mul(1 << (failures - 1).min(16)),
+	};
+
+	backoff.min(WATCH_MAX_WAIT)
+}
+
+/// Checkpoint and buffer of a mentions watcher.
+struct WatchState {
+	params: TimelineParams,
+	options: RequestOptions,
+	pending: VecDeque<TweetData>,
+	seen: VecDeque<String>,
+	polled: bool,
+	failures: u32,
+	backoff: Option<Duration>,
+	done: bool,
+}
+impl WatchState {
+	/// Fetches every mention newer than the checkpoint and queues the unseen ones, oldest first.
+	///
+	/// Without a checkpoint, only the newest mention is looked up to start from now.
+	async fn poll<A>(&mut self, api: &Api<A>, user_id: &str, fields: &Fields) -> Result<()>
+	where
+		A: AuthProvider,
+	{
+		let start = !self.polled && self.params.since_id.is_none();
+		let mut params = self.params.clone();
+		let mut newest_id = None;
+		let mut tweets = Vec::new();
+
+		if start {
+			params.max_results = Some(5);
+		}
+
+		loop {
+			let page = api
+				.user_mentions_with_options(user_id, &params, fields, &self.options)
+				.await?
+				.into_result()?;
+
+			if newest_id.is_none() {
+				newest_id = page.meta.newest_id;
+			}
+
+			tweets.extend(page.data.unwrap_or_default());
+
+			match page.meta.next_token {
+				Some(next_token) if !start => params.pagination_token = Some(next_token),
+				_ => break,
+			}
+		}
+
+		self.polled = true;
+
+		if !start {
+			for tweet in tweets.into_iter().rev() {
+				if self.seen.contains(&tweet.id) {
+					continue;
+				}
+				if self.seen.len() == WATCH_SEEN_CAPACITY {
+					self.seen.pop_front();
+				}
+
+				self.seen.push_back(tweet.id.clone());
+				self.pending.push_back(tweet);
+			}
+		}
+		if newest_id.is_some() {
+			self.params.since_id = newest_id;
+		}
+
+		Ok(())
+	}
+}
+
+/// Time range, ID range and pagination of a timeline.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TimelineParams {
+	/// Number of tweets per page, 5 to 100.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_results: Option<u32>,
+	/// Oldest time of the tweets, inclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub start_time: Option<Timestamp>,
+	/// Newest time of the tweets, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub end_time: Option<Timestamp>,
+	/// Only tweets newer than the tweet, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub since_id: Option<String>,
+	/// Only tweets older than the tweet, exclusive.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub until_id: Option<String>,
+	/// Kinds of tweets left out, only supported by user tweets and not sent for mentions.
+	#[serde(
+		default,
+		serialize_with = "comma_separated",
+		deserialize_with = "from_comma_separated",
+		skip_serializing_if = "BTreeSet::is_empty"
+	)]
+	pub exclude: BTreeSet<Exclude>,
+	/// Token of the page to fetch, `meta.next_token` of the previous page.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pagination_token: Option<String>,
+}
+impl TimelineParams {
+	/// Creates parameters fetching the first page of the default time range.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the number of tweets per page.
+	pub fn max_results(mut self, max_results: u32) -> Self {
+		self.max_results = Some(max_results);
+
+		self
+	}
+
+	/// Only returns tweets posted at or after the time.
+	pub fn start_time(mut self, start_time: Timestamp) -> Self {
+		self.start_time = Some(start_time);
+
+		self
+	}
+
+	/// Only returns tweets posted at or after the time.
+	#[cfg(feature = "chrono")]
+	pub fn start_time_utc(self, start_time: chrono::DateTime<chrono::Utc>) -> Self {
+		self.start_time(time::format_timestamp(start_time))
+	}
+
+	/// Only returns tweets posted before the time.
+	pub fn end_time(mut self, end_time: Timestamp) -> Self {
+		self.end_time = Some(end_time);
+
+		self
+	}
+
+	/// Only returns tweets posted before the time.
+	#[cfg(feature = "chrono")]
+	pub fn end_time_utc(self, end_time: chrono::DateTime<chrono::Utc>) -> Self {
+		self.end_time(time::format_timestamp(end_time))
+	}
+
+	/// Only returns tweets newer than the tweet, e.g. the newest one of the last poll.
+	pub fn since_id(mut self, since_id: String) -> Self {
+		self.since_id = Some(since_id);
+
+		self
+	}
+
+	/// Only returns tweets older than the tweet.
+	pub fn until_id(mut self, until_id: String) -> Self {
+		self.until_id = Some(until_id);
+
+		self
+	}
+
+	/// Leaves out the kinds of tweets, e.g. to fetch original posts only, ignored by mentions.
+	pub fn exclude<I>(mut self, exclude: I) -> Self
+	where
+		I: IntoIterator<Item = Exclude>,
+	{
+		self.exclude.extend(exclude);
+
+		self
+	}
+
+	/// Fetches the page of the token.
+	pub fn pagination_token(mut self, pagination_token: String) -> Self {
+		self.pagination_token = Some(pagination_token);
+
+		self
+	}
+}
+
+/// Kind of tweets left out of a timeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Exclude {
+	/// Replies to other tweets.
+	Replies,
+	/// Retweets of other tweets.
+	Retweets,
+}
+impl Exclude {
+	/// Returns the name sent to the API.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Replies => "replies",
+			Self::Retweets => "retweets",
+		}
+	}
+}
+impl Display for Exclude {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		f.write_str(self.as_str())
+	}
+}
+impl FromStr for Exclude {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s {
+			"replies" => Ok(Self::Replies),
+			"retweets" => Ok(Self::Retweets),
+			_ => Err(Error::any(format!("unknown exclude: {s}"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	#[test]
+	fn watch_backoff_grows_exponentially_up_to_max_wait() {
+		let e = Error::any("failed");
+		let interval = Duration::from_secs(60);
+
+		assert_eq!(watch_backoff(&e, interval, 1), interval);
+		assert_eq!(watch_backoff(&e, interval, 2), interval * 2);
+		assert_eq!(watch_backoff(&e, interval, 4), interval * 8);
+		assert_eq!(watch_backoff(&e, interval, 5), WATCH_MAX_WAIT);
+		assert_eq!(watch_backoff(&e, interval, u32::MAX), WATCH_MAX_WAIT);
+		assert_eq!(watch_backoff(&e, Duration::ZERO, 1), Duration::from_secs(1));
+	}
+
+	#[test]
+	fn watch_backoff_waits_for_retry_after() {
+		let e = |retry_in| Error::CircuitOpen {
+			endpoint: "GET /2/users/:id/mentions".into(),
+			retry_in,
+		};
+
+		assert_eq!(
+			watch_backoff(&e(Duration::from_secs(30)), Duration::ZERO, 3),
+			Duration::from_secs(31)
+		);
+		assert_eq!(watch_backoff(&e(Duration::from_secs(3600)), Duration::ZERO, 1), WATCH_MAX_WAIT);
+	}
+
+	#[test]
+	fn exclude_round_trips_through_strings() {
+		for exclude in [Exclude::Replies, Exclude::Retweets] {
+			assert_eq!(exclude.to_string().parse::<Exclude>().unwrap(), exclude);
+		}
+
+		assert!("Replies".parse::<Exclude>().is_err());
+		assert!("replies,retweets".parse::<Exclude>().is_err());
+	}
+}
@@ -0,0 +1,148 @@
+//! HTTP transports executing API requests.
+
+// std
+use std::fmt::Debug;
+#[cfg(not(target_arch = "wasm32"))] use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+// crates.io
+use reqwest::{Client, Request, Response};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{
+	Method, ResponseBuilderExt, StatusCode,
+	header::{CONTENT_TYPE, HeaderMap},
+};
+// self
+use crate::prelude::*;
+
+/// Transport sending built requests, the [`reqwest::Client`] by default.
+///
+/// Replace it with a `MockTransport` to unit-test code using [`Api`] without network access.
+pub trait HttpTransport: Debug + Send + Sync {
+	/// Sends the request and resolves to its response.
+	fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>>;
+}
+impl HttpTransport for Client {
+	fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+		Box::pin(async move { Ok(Client::execute(self, request).await?) })
+	}
+}
+
+/// Transport answering requests with canned responses keyed by method and path, e.g.
+/// `POST /2/tweets`, and recording every request for later assertions.
+///
+/// Requests without a canned response fail.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+	responses: Arc<Mutex<HashMap<(Method, String), MockResponse>>>,
+	requests: Arc<Mutex<Vec<Request>>>,
+}
+#[cfg(not(target_arch = "wasm32"))]
+impl MockTransport {
+	/// Creates a transport without canned responses.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Answers requests to the path, e.g. `/2/tweets`, with the status and body.
+	pub fn with_response<P, B>(self, method: Method, path: P, status: StatusCode, body: B) -> Self
+	where
+		P: Into<String>,
+		B: Into<String>,
+	{
+		self.with_response_headers(method, path, status, HeaderMap::new(), body)
+	}
+
+	/// Answers requests to the path, e.g. `/2/tweets`, with the status, headers and body, e.g. to
+	/// simulate rate limits.
+	pub fn with_response_headers<P, B>(
+		self,
+		method: Method,
+		path: P,
+		status: StatusCode,
+		headers: HeaderMap,
+		body: B,
+	) -> Self
+	where
+		P: Into<String>,
+		B: Into<String>,
+	{
+		self.responses
+			.lock()
+			.expect("lock must not be poisoned; qed")
+			.insert((method, path.into()), MockResponse { status, headers, body: body.into() });
+
+		self
+	}
+
+	/// Answers requests to the path, e.g. `/2/tweets`, with the status and JSON body.
+	pub fn with_json<P, B>(self, method: Method, path: P, status: StatusCode, body: &B) -> Self
+	where
+		P: Into<String>,
+		B: ?Sized + Serialize,
+	{
+		let body = serde_json::to_string(body).expect("JSON body must be serializable; qed");
+
+		self.with_response(method, path, status, body)
+	}
+
+	/// Returns the requests sent so far, in order.
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests
+			.lock()
+			.expect("lock must not be poisoned; qed")
+			.iter()
+			.filter_map(Request::try_clone)
+			.collect()
+	}
+}
+#[cfg(not(target_arch = "wasm32"))]
+impl HttpTransport for MockTransport {
+	fn execute(&self, request: Request) -> BoxFuture<'_, Result<Response>> {
+		Box::pin(async move {
+			let key = (request.method().to_owned(), request.url().path().to_owned());
+			let url = request.url().to_owned();
+			let response = self
+				.responses
+				.lock()
+				.expect("lock must not be poisoned; qed")
+				.get(&key)
+				.cloned()
+				.ok_or_else(|| Error::any(format!("no mock response for {} {}", key.0, key.1)))?;
+
+			self.requests.lock().expect("lock must not be poisoned; qed").push(request);
+
+			build_response(url, response.status, response.headers, response.body)
+		})
+	}
+}
+
+/// Builds a JSON response as if it had been received from the URL.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn build_response(
+	url: reqwest::Url,
+	status: StatusCode,
+	headers: HeaderMap,
+	body: String,
+) -> Result<Response> {
+	let mut builder =
+		http::Response::builder().status(status).header(CONTENT_TYPE, "application/json").url(url);
+
+	if let Some(h) = builder.headers_mut() {
+		h.extend(headers);
+	}
+
+	let response = builder.body(body).map_err(|e| Error::any(format!("invalid response: {e}")))?;
+
+	Ok(response.into())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+struct MockResponse {
+	status: StatusCode,
+	headers: HeaderMap,
+	body: String,
+}
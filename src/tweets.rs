@@ -1,34 +1,669 @@
 //! X/Twitter V2 Tweets API
 
+// std
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::{Display, Formatter, Result as FmtResult},
+	pin,
+};
 // crates.io
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 // self
-use crate::{ApiResponse, prelude::*};
+use crate::{
+	ApiResponse, Envelope, Expansion, Fields, FieldsQuery, Includes, Pagination, Paginator,
+	PartialError, RequestOptions, Timestamp, TweetField, XErrorKind,
+	auth::{AuthProvider, Scopes},
+	links::{self, WEB_BASE},
+	prelude::*,
+	search::{AccessLevel, ApiSearch, Query, SearchParams},
+	users::UserObject,
+};
 
-/// Trait for posting tweets to X/Twitter API.
+/// Scopes required to post a tweet.
+const TWEET_SCOPES: &[&str] = &[Scopes::TWEET_READ, Scopes::TWEET_WRITE, Scopes::USERS_READ];
+/// Scopes required to look up tweets.
+const TWEET_READ_SCOPES: &[&str] = &[Scopes::TWEET_READ, Scopes::USERS_READ];
+
+/// Trait for posting and looking up tweets on X/Twitter API.
 pub trait ApiTweet {
 	/// Posts a tweet with the given text content.
-	fn tweet(&self, text: String) -> impl Send + Future<Output = Result<ApiResponse<TweetObject>>>;
+	fn tweet(
+		&self,
+		text: String,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Posts a tweet with the given text content, overriding the client configuration for this
+	/// call.
+	fn tweet_with_options(
+		&self,
+		text: String,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Posts a reply to the tweet with the given text content.
+	fn reply(
+		&self,
+		text: String,
+		tweet_id: String,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Posts a reply to the tweet with the given text content, overriding the client
+	/// configuration for this call.
+	fn reply_with_options(
+		&self,
+		text: String,
+		tweet_id: String,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Posts a tweet described by the request, e.g. a reply excluding some users.
+	fn create_tweet(
+		&self,
+		request: TweetRequest,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Posts a tweet described by the request, overriding the client configuration for this
+	/// call.
+	fn create_tweet_with_options(
+		&self,
+		request: TweetRequest,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Looks up a tweet by ID with the requested fields and expansions, the expanded objects
+	/// being returned in [`Envelope::includes`].
+	fn get_tweet(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Looks up a tweet by ID with the requested fields and expansions, overriding the client
+	/// configuration for this call.
+	fn get_tweet_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Looks up a tweet by ID together with every version of its edit chain in
+	/// `includes.tweets`, see [`TweetData::edit_chain`].
+	fn edit_history(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Looks up a tweet by ID together with every version of its edit chain, overriding the
+	/// client configuration for this call.
+	fn edit_history_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<TweetData>>>>;
+
+	/// Lists the tweets quoting the tweet, one page at a time.
+	fn quote_tweets(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Lists the tweets quoting the tweet, one page at a time, overriding the client
+	/// configuration for this call.
+	fn quote_tweets_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<TweetData>>>>>;
+
+	/// Lists the users who retweeted the tweet, one page at a time.
+	fn retweeted_by(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<UserObject>>>>>;
+
+	/// Lists the users who retweeted the tweet, one page at a time, overriding the client
+	/// configuration for this call.
+	fn retweeted_by_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<Vec<UserObject>>>>>;
+
+	/// Reconstructs the thread the tweet belongs to as a reply tree.
+	///
+	/// The thread is collected through recent search, so replies older than 7 days are missing.
+	fn conversation(
+		&self,
+		tweet_id: &str,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<Conversation>>;
+
+	/// Reconstructs the thread the tweet belongs to as a reply tree, overriding the client
+	/// configuration for this call.
+	fn conversation_with_options(
+		&self,
+		tweet_id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<Conversation>>;
 }
 /// Implementation of tweet posting functionality for the main API client.
-impl ApiTweet for Api {
-	async fn tweet(&self, text: String) -> Result<ApiResponse<TweetObject>> {
-		self.post("https://api.x.com/2/tweets", &TweetRequest { text }).await
+impl<A> ApiTweet for Api<A>
+where
+	A: AuthProvider,
+{
+	async fn tweet(&self, text: String) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.tweet_with_options(text, &RequestOptions::default()).await
+	}
+
+	async fn tweet_with_options(
+		&self,
+		text: String,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.create_tweet_with_options(TweetRequest::new(text), options).await
+	}
+
+	async fn reply(
+		&self,
+		text: String,
+		tweet_id: String,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.reply_with_options(text, tweet_id, &RequestOptions::default()).await
+	}
+
+	async fn reply_with_options(
+		&self,
+		text: String,
+		tweet_id: String,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.create_tweet_with_options(TweetRequest::new(text).reply_to(tweet_id), options).await
+	}
+
+	async fn create_tweet(
+		&self,
+		request: TweetRequest,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.create_tweet_with_options(request, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+	async fn create_tweet_with_options(
+		&self,
+		request: TweetRequest,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		request.validate()?;
+		self.authenticator.ensure_scopes(TWEET_SCOPES)?;
+
+		self.post_with_options(&self.url("/2/tweets"), &request, options).await
+	}
+
+	async fn get_tweet(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.get_tweet_with_options(id, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = %id)))]
+	async fn get_tweet_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.authenticator.ensure_scopes(TWEET_READ_SCOPES)?;
+
+		let url = self.url(&format!("/2/tweets/{}", urlencoding::encode(id)));
+
+		self.get_with_options(&url, fields, options).await
+	}
+
+	async fn edit_history(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		self.edit_history_with_options(id, fields, &RequestOptions::default()).await
+	}
+
+	async fn edit_history_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<TweetData>>> {
+		let fields = fields
+			.clone()
+			.tweet_fields([TweetField::EditHistoryTweetIds, TweetField::EditControls])
+			.expansions([Expansion::EditHistoryTweetIds]);
+
+		self.get_tweet_with_options(id, &fields, options).await
+	}
+
+	async fn quote_tweets(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.quote_tweets_with_options(tweet_id, pagination, fields, &RequestOptions::default())
+			.await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(tweet_id = %tweet_id)))]
+	async fn quote_tweets_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<TweetData>>>> {
+		self.authenticator.ensure_scopes(TWEET_READ_SCOPES)?;
+
+		let url = self.url(&format!("/2/tweets/{}/quote_tweets", urlencoding::encode(tweet_id)));
+
+		self.get_with_options(&url, &FieldsQuery { params: pagination, fields }, options).await
+	}
+
+	async fn retweeted_by(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<Vec<UserObject>>>> {
+		self.retweeted_by_with_options(tweet_id, pagination, fields, &RequestOptions::default())
+			.await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(tweet_id = %tweet_id)))]
+	async fn retweeted_by_with_options(
+		&self,
+		tweet_id: &str,
+		pagination: &Pagination,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<Vec<UserObject>>>> {
+		self.authenticator.ensure_scopes(TWEET_READ_SCOPES)?;
+
+		let url = self.url(&format!("/2/tweets/{}/retweeted_by", urlencoding::encode(tweet_id)));
+
+		self.get_with_options(&url, &FieldsQuery { params: pagination, fields }, options).await
+	}
+
+	async fn conversation(&self, tweet_id: &str, fields: &Fields) -> Result<Conversation> {
+		self.conversation_with_options(tweet_id, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(tweet_id = %tweet_id)))]
+	async fn conversation_with_options(
+		&self,
+		tweet_id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<Conversation> {
+		let fields =
+			fields.clone().tweet_fields([TweetField::ConversationId, TweetField::ReferencedTweets]);
+		let tweet = self
+			.get_tweet_with_options(tweet_id, &fields, options)
+			.await?
+			.into_result()?
+			.into_data()?;
+		let id = tweet.conversation_id.clone().unwrap_or_else(|| tweet.id.clone());
+		let (root, errors) = if id == tweet.id {
+			(Some(tweet), Vec::new())
+		} else {
+			// The root may have been deleted while its replies remain.
+			match self.get_tweet_with_options(&id, &fields, options).await?.into_result() {
+				Ok(root) => (root.data, root.errors),
+				Err(Error::Api { api_error, .. })
+					if matches!(
+						api_error.kind(),
+						XErrorKind::ResourceNotFound | XErrorKind::NotAuthorizedForResource
+					) =>
+					(None, api_error.errors),
+				Err(e) => Err(e)?,
+			}
+		};
+		let query = Query::new().conversation_id(&id).build(AccessLevel::Basic)?;
+		let replies = Paginator::new(|next_token| {
+			let params = SearchParams { max_results: Some(100), next_token, ..Default::default() };
+			let (query, fields) = (&query, &fields);
+
+			async move { self.search_recent_with_options(query, &params, fields, options).await }
+		})
+		.items();
+		let mut replies = pin::pin!(replies);
+		let mut tweets = Vec::new();
+
+		while let Some(reply) = replies.next().await {
+			tweets.push(reply?);
+		}
+
+		Ok(Conversation { errors, ..Conversation::build(id, root, tweets) })
+	}
+}
+
+/// Returns the tweet ID of a pasted link, e.g. `https://x.com/jack/status/20`, or of a bare ID.
+///
+/// Accepts `x.com` and `twitter.com` links with or without a scheme, `www.`/`mobile.` hosts,
+/// trailing segments like `/photo/1` and query strings.
+pub fn parse_tweet_id(link: &str) -> Result<String> {
+	let link = link.trim();
+
+	if links::is_id(link) {
+		return Ok(link.into());
 	}
+
+	links::path_segments(link)
+		.and_then(|(_, segments)| {
+			segments
+				.windows(2)
+				.find(|w| matches!(w[0].as_str(), "status" | "statuses") && links::is_id(&w[1]))
+				.map(|w| w[1].clone())
+		})
+		.ok_or_else(|| Error::any(format!("not a tweet link: {link}")))
 }
 
 /// Request payload for creating a new tweet.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TweetRequest {
 	/// The text content of the tweet to be posted.
 	pub text: String,
+	/// The tweet replied to, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reply: Option<TweetReply>,
+	/// The poll attached to the tweet, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub poll: Option<TweetPoll>,
+	/// Who can reply to the tweet, everyone if unset.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reply_settings: Option<ReplySettings>,
+	/// The place the tweet is tagged with, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub geo: Option<TweetGeo>,
+	/// The community the tweet is posted to, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub community_id: Option<String>,
+	/// Whether only super followers of the author can see the tweet.
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub for_super_followers_only: bool,
 }
+impl TweetRequest {
+	/// Creates a request posting the text.
+	pub fn new(text: String) -> Self {
+		Self {
+			text,
+			reply: None,
+			poll: None,
+			reply_settings: None,
+			geo: None,
+			community_id: None,
+			for_super_followers_only: false,
+		}
+	}
 
-/// Response object containing tweet data from the API.
-#[derive(Debug, Deserialize)]
-pub struct TweetObject {
-	/// The actual tweet data returned by the API.
-	pub data: TweetData,
+	/// Posts the tweet as a reply to the tweet.
+	pub fn reply_to(mut self, tweet_id: String) -> Self {
+		self.reply =
+			Some(TweetReply { in_reply_to_tweet_id: tweet_id, exclude_reply_user_ids: Vec::new() });
+
+		self
+	}
+
+	/// Excludes the user from the mentions of the reply, e.g. to not notify everyone in a thread.
+	///
+	/// Only takes effect after [`TweetRequest::reply_to`].
+	pub fn exclude_reply_user(mut self, user_id: String) -> Self {
+		if let Some(reply) = &mut self.reply {
+			reply.exclude_reply_user_ids.push(user_id);
+		}
+
+		self
+	}
+
+	/// Attaches a poll with the options, open for the duration in minutes.
+	pub fn poll<I>(mut self, options: I, duration_minutes: u32) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<String>,
+	{
+		self.poll = Some(TweetPoll {
+			options: options.into_iter().map(Into::into).collect(),
+			duration_minutes,
+			reply_settings: None,
+		});
+
+		self
+	}
+
+	/// Limits who can reply to the poll.
+	///
+	/// Only takes effect after [`TweetRequest::poll`]. Both settings apply to the same tweet, so
+	/// they must agree if [`TweetRequest::reply_settings`] is set too.
+	pub fn poll_reply_settings(mut self, reply_settings: ReplySettings) -> Self {
+		if let Some(poll) = &mut self.poll {
+			poll.reply_settings = Some(reply_settings);
+		}
+
+		self
+	}
+
+	/// Limits who can reply to the tweet, e.g. for automated posts.
+	pub fn reply_settings(mut self, reply_settings: ReplySettings) -> Self {
+		self.reply_settings = Some(reply_settings);
+
+		self
+	}
+
+	/// Tags the tweet with the place, e.g. one from a place search.
+	pub fn place(mut self, place_id: String) -> Self {
+		self.geo = Some(TweetGeo { place_id });
+
+		self
+	}
+
+	/// Posts the tweet to the community instead of the home timeline.
+	pub fn community(mut self, community_id: String) -> Self {
+		self.community_id = Some(community_id);
+
+		self
+	}
+
+	/// Restricts the tweet to super followers of the author.
+	pub fn for_super_followers_only(mut self, for_super_followers_only: bool) -> Self {
+		self.for_super_followers_only = for_super_followers_only;
+
+		self
+	}
+
+	/// Checks the constraints X enforces, failing before a request is sent.
+	pub fn validate(&self) -> Result<()> {
+		if let Some(poll) = &self.poll {
+			poll.validate()?;
+
+			if let (Some(poll), Some(tweet)) = (poll.reply_settings, self.reply_settings)
+				&& poll != tweet
+			{
+				Err(Error::any(format!(
+					"poll reply settings {poll:?} conflict with tweet reply settings {tweet:?}"
+				)))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Reply settings of a new tweet.
+#[derive(Clone, Debug, Serialize)]
+pub struct TweetReply {
+	/// ID of the tweet replied to.
+	pub in_reply_to_tweet_id: String,
+	/// IDs of users mentioned in the thread who are not mentioned in the reply.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub exclude_reply_user_ids: Vec<String>,
+}
+
+/// Who can reply to a tweet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplySettings {
+	/// Anyone can reply.
+	Everyone,
+	/// Only users mentioned in the tweet can reply.
+	MentionedUsers,
+	/// Only users followed by the author can reply.
+	Following,
+	/// Only subscribers of the author can reply.
+	Subscribers,
+}
+
+/// Poll of a new tweet.
+#[derive(Clone, Debug, Serialize)]
+pub struct TweetPoll {
+	/// Labels of the choices, 2 to 4 of them.
+	pub options: Vec<String>,
+	/// How long the poll accepts votes, 5 minutes to 7 days.
+	pub duration_minutes: u32,
+	/// Who can reply to the poll, matching the reply settings of the tweet if both are set.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reply_settings: Option<ReplySettings>,
+}
+impl TweetPoll {
+	/// Maximum poll duration in minutes, 7 days.
+	pub const MAX_DURATION_MINUTES: u32 = 10_080;
+	/// Maximum number of poll options.
+	pub const MAX_OPTIONS: usize = 4;
+	/// Minimum poll duration in minutes.
+	pub const MIN_DURATION_MINUTES: u32 = 5;
+	/// Minimum number of poll options.
+	pub const MIN_OPTIONS: usize = 2;
+
+	fn validate(&self) -> Result<()> {
+		if !(Self::MIN_OPTIONS..=Self::MAX_OPTIONS).contains(&self.options.len()) {
+			Err(Error::any(format!(
+				"poll must have {} to {} options, got {}",
+				Self::MIN_OPTIONS,
+				Self::MAX_OPTIONS,
+				self.options.len()
+			)))?;
+		}
+		if !(Self::MIN_DURATION_MINUTES..=Self::MAX_DURATION_MINUTES)
+			.contains(&self.duration_minutes)
+		{
+			Err(Error::any(format!(
+				"poll duration must be {} to {} minutes, got {}",
+				Self::MIN_DURATION_MINUTES,
+				Self::MAX_DURATION_MINUTES,
+				self.duration_minutes
+			)))?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Poll attached to a tweet, included through the `attachments.poll_ids` expansion.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Poll {
+	/// Unique identifier for the poll.
+	pub id: String,
+	/// Choices of the poll.
+	pub options: Vec<PollOption>,
+	/// How long the poll accepts votes.
+	pub duration_minutes: Option<u32>,
+	/// When the poll stops accepting votes.
+	pub end_datetime: Option<Timestamp>,
+	/// Whether the poll still accepts votes.
+	pub voting_status: Option<PollVotingStatus>,
+}
+impl Poll {
+	/// Returns when the poll stops accepting votes, parsed.
+	#[cfg(feature = "chrono")]
+	pub fn end_datetime_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+		self.end_datetime.as_deref().and_then(crate::time::parse_timestamp)
+	}
+}
+
+/// Choice of a poll.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PollOption {
+	/// 1-based position of the option.
+	pub position: u32,
+	/// Label of the option.
+	pub label: String,
+	/// Number of votes cast for the option.
+	#[serde(default)]
+	pub votes: u64,
+}
+
+/// Voting status of a poll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PollVotingStatus {
+	/// The poll accepts votes.
+	Open,
+	/// The poll ended.
+	Closed,
+}
+
+/// Location of a new tweet.
+#[derive(Clone, Debug, Serialize)]
+pub struct TweetGeo {
+	/// ID of the place the tweet is tagged with.
+	pub place_id: String,
+}
+
+/// Place tagged in a tweet, included through the `geo.place_id` expansion.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Place {
+	/// Unique identifier for the place.
+	pub id: String,
+	/// Long name of the place, e.g. `Manhattan, NY`.
+	pub full_name: String,
+	/// Short name of the place, e.g. `Manhattan`.
+	pub name: Option<String>,
+	/// Kind of the place, e.g. `city` or `poi`.
+	pub place_type: Option<String>,
+	/// Name of the country containing the place.
+	pub country: Option<String>,
+	/// ISO 3166-1 alpha-2 code of the country containing the place.
+	pub country_code: Option<String>,
+	/// IDs of places containing the place.
+	#[serde(default)]
+	pub contained_within: Vec<String>,
+	/// GeoJSON of the place.
+	pub geo: Option<PlaceGeo>,
+}
+
+/// GeoJSON feature of a place.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PlaceGeo {
+	/// GeoJSON type, e.g. `Feature`.
+	pub r#type: String,
+	/// Bounding box as west longitude, south latitude, east longitude and north latitude.
+	#[serde(default)]
+	pub bbox: Vec<f64>,
+	/// Properties of the feature.
+	#[serde(default)]
+	pub properties: Map<String, Value>,
 }
 
 /// Core tweet data structure containing tweet information.
@@ -38,4 +673,530 @@ pub struct TweetData {
 	pub id: String,
 	/// The text content of the tweet.
 	pub text: String,
+	/// Hashtags, cashtags, mentions and URLs parsed out of the text.
+	pub entities: Option<TweetEntities>,
+	/// Topics X inferred from the tweet.
+	#[serde(default)]
+	pub context_annotations: Vec<ContextAnnotation>,
+	/// Tweets retweeted, quoted or replied to by the tweet.
+	#[serde(default)]
+	pub referenced_tweets: Vec<ReferencedTweet>,
+	/// ID of the tweet starting the thread the tweet belongs to.
+	pub conversation_id: Option<String>,
+	/// ID of the author of the tweet replied to.
+	pub in_reply_to_user_id: Option<String>,
+	/// Full text of a tweet longer than 280 characters, whose `text` is truncated.
+	pub note_tweet: Option<NoteTweet>,
+	/// When the tweet was posted.
+	pub created_at: Option<Timestamp>,
+	/// BCP 47 language tag detected for the text, `und` if undetermined.
+	pub lang: Option<String>,
+	/// Name of the app the tweet was posted from, if X still reports it.
+	pub source: Option<String>,
+	/// Whether the tweet links to content that may be sensitive.
+	pub possibly_sensitive: Option<bool>,
+	/// IDs of every version of the tweet, oldest first, the last one being the latest.
+	#[serde(default)]
+	pub edit_history_tweet_ids: Vec<String>,
+	/// Whether and until when the tweet can still be edited.
+	pub edit_controls: Option<EditControls>,
+	/// Engagement counts visible to everyone.
+	pub public_metrics: Option<PublicTweetMetrics>,
+	/// Engagement counts visible to the author only, requiring user context.
+	pub non_public_metrics: Option<NonPublicTweetMetrics>,
+	/// Engagement counts outside of promotion, requiring user context.
+	pub organic_metrics: Option<EngagementMetrics>,
+	/// Engagement counts within promotion, requiring user context.
+	pub promoted_metrics: Option<EngagementMetrics>,
+	/// Fields not modeled yet, e.g. ones added by X recently.
+	#[serde(flatten)]
+	pub extra: Map<String, Value>,
+}
+impl TweetData {
+	/// Returns when the tweet was posted, parsed.
+	#[cfg(feature = "chrono")]
+	pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+		self.created_at.as_deref().and_then(crate::time::parse_timestamp)
+	}
+
+	/// Returns the untruncated text, from `note_tweet` for long tweets.
+	pub fn full_text(&self) -> &str {
+		self.note_tweet.as_ref().map_or(&self.text, |note| &note.text)
+	}
+
+	/// Returns whether the tweet is a version of an edited tweet.
+	pub fn is_edited(&self) -> bool {
+		self.edit_history_tweet_ids.len() > 1
+	}
+
+	/// Returns the versions of the tweet found in the includes, oldest first, e.g. from
+	/// [`ApiTweet::edit_history`].
+	pub fn edit_chain<'a>(&'a self, includes: &'a Includes) -> Vec<&'a TweetData> {
+		self.edit_history_tweet_ids
+			.iter()
+			.filter_map(|id| {
+				if id == &self.id {
+					Some(self)
+				} else {
+					includes.tweets.iter().find(|t| &t.id == id)
+				}
+			})
+			.collect()
+	}
+
+	/// Returns the ID of the tweet referenced in the way, e.g. the quoted one.
+	pub fn referenced_tweet(&self, r#type: ReferenceType) -> Option<&str> {
+		self.referenced_tweets.iter().find(|t| t.r#type == r#type).map(|t| t.id.as_str())
+	}
+
+	/// Returns the link to the tweet under the author's handle.
+	///
+	/// Use the [`Display`] impl for a link that does not need the handle.
+	pub fn permalink(&self, username: &str) -> String {
+		format!("{WEB_BASE}/{}/status/{}", username.trim_start_matches('@'), self.id)
+	}
+}
+/// Formats the tweet as its permalink, `https://x.com/i/web/status/<id>`.
+impl Display for TweetData {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		write!(f, "{WEB_BASE}/i/web/status/{}", self.id)
+	}
+}
+
+/// Edit state of a tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct EditControls {
+	/// How many more times the tweet can be edited.
+	pub edits_remaining: u32,
+	/// When the edit window of the tweet closes.
+	pub editable_until: Timestamp,
+	/// Whether the tweet could be edited when it was posted, e.g. not for polls.
+	pub is_edit_eligible: bool,
+}
+impl EditControls {
+	/// Returns when the edit window of the tweet closes, parsed.
+	#[cfg(feature = "chrono")]
+	pub fn editable_until_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+		crate::time::parse_timestamp(&self.editable_until)
+	}
+}
+
+/// Tweet referenced by another tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReferencedTweet {
+	/// How the tweet is referenced.
+	pub r#type: ReferenceType,
+	/// ID of the referenced tweet.
+	pub id: String,
+}
+
+/// How a tweet references another tweet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceType {
+	/// The tweet is a retweet.
+	Retweeted,
+	/// The tweet quotes the other tweet.
+	Quoted,
+	/// The tweet replies to the other tweet.
+	RepliedTo,
+}
+
+/// Public engagement counts of a tweet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct PublicTweetMetrics {
+	/// Number of retweets.
+	#[serde(default)]
+	pub retweet_count: u64,
+	/// Number of replies.
+	#[serde(default)]
+	pub reply_count: u64,
+	/// Number of likes.
+	#[serde(default)]
+	pub like_count: u64,
+	/// Number of quote tweets.
+	#[serde(default)]
+	pub quote_count: u64,
+	/// Number of bookmarks.
+	#[serde(default)]
+	pub bookmark_count: u64,
+	/// Number of times the tweet was viewed.
+	#[serde(default)]
+	pub impression_count: u64,
+}
+
+/// Private engagement counts of a tweet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct NonPublicTweetMetrics {
+	/// Number of times the tweet was viewed.
+	#[serde(default)]
+	pub impression_count: u64,
+	/// Number of clicks on links in the tweet.
+	#[serde(default)]
+	pub url_link_clicks: u64,
+	/// Number of clicks on the profile of the author.
+	#[serde(default)]
+	pub user_profile_clicks: u64,
+}
+
+/// Organic or promoted engagement counts of a tweet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct EngagementMetrics {
+	/// Number of times the tweet was viewed.
+	#[serde(default)]
+	pub impression_count: u64,
+	/// Number of retweets.
+	#[serde(default)]
+	pub retweet_count: u64,
+	/// Number of replies.
+	#[serde(default)]
+	pub reply_count: u64,
+	/// Number of likes.
+	#[serde(default)]
+	pub like_count: u64,
+	/// Number of clicks on links in the tweet.
+	#[serde(default)]
+	pub url_link_clicks: u64,
+	/// Number of clicks on the profile of the author.
+	#[serde(default)]
+	pub user_profile_clicks: u64,
+}
+
+/// Long-form content of a tweet longer than 280 characters.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NoteTweet {
+	/// The full text content.
+	pub text: String,
+	/// Entities parsed out of the full text.
+	pub entities: Option<TweetEntities>,
+}
+
+/// Entities parsed out of the text of a tweet.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TweetEntities {
+	/// Hashtags, without the leading `#`.
+	#[serde(default)]
+	pub hashtags: Vec<TagEntity>,
+	/// Cashtags, without the leading `$`.
+	#[serde(default)]
+	pub cashtags: Vec<TagEntity>,
+	/// Mentioned users.
+	#[serde(default)]
+	pub mentions: Vec<MentionEntity>,
+	/// Links, shortened to `t.co` in the text.
+	#[serde(default)]
+	pub urls: Vec<UrlEntity>,
+	/// Named entities, e.g. people, places and products, recognized in the text.
+	#[serde(default)]
+	pub annotations: Vec<AnnotationEntity>,
+}
+
+/// Hashtag or cashtag in a tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TagEntity {
+	/// Start index of the tag in the text, inclusive.
+	pub start: usize,
+	/// End index of the tag in the text, exclusive.
+	pub end: usize,
+	/// The tag without its leading symbol.
+	pub tag: String,
+}
+
+/// Mention of a user in a tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct MentionEntity {
+	/// Start index of the mention in the text, inclusive.
+	pub start: usize,
+	/// End index of the mention in the text, exclusive.
+	pub end: usize,
+	/// Username of the mentioned user, without the leading `@`.
+	pub username: String,
+	/// ID of the mentioned user.
+	pub id: Option<String>,
+}
+
+/// Link in a tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct UrlEntity {
+	/// Start index of the link in the text, inclusive.
+	pub start: usize,
+	/// End index of the link in the text, exclusive.
+	pub end: usize,
+	/// The `t.co` link as it appears in the text.
+	pub url: String,
+	/// Full link the `t.co` link points to.
+	pub expanded_url: Option<String>,
+	/// Shortened link shown to users.
+	pub display_url: Option<String>,
+	/// Final link after following redirects.
+	pub unwound_url: Option<String>,
+	/// Key of the media the link points to, if it is an attachment.
+	pub media_key: Option<String>,
+	/// HTTP status of the final link.
+	pub status: Option<u16>,
+	/// Title of the linked page.
+	pub title: Option<String>,
+	/// Description of the linked page.
+	pub description: Option<String>,
+}
+
+/// Named entity recognized in the text of a tweet.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnnotationEntity {
+	/// Start index of the entity in the text, inclusive.
+	pub start: usize,
+	/// End index of the entity in the text, exclusive.
+	pub end: usize,
+	/// Confidence of the recognition, from 0 to 1.
+	pub probability: f64,
+	/// Kind of the entity, e.g. `Person`, `Place` or `Product`.
+	pub r#type: String,
+	/// Text of the entity.
+	pub normalized_text: String,
+}
+
+/// Topic inferred from a tweet, as an entity within a domain.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContextAnnotation {
+	/// Domain of the topic, e.g. `Brand`.
+	pub domain: ContextEntity,
+	/// The topic within the domain, e.g. a specific brand.
+	pub entity: ContextEntity,
+}
+
+/// Domain or entity of a context annotation.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContextEntity {
+	/// Unique identifier, stable across tweets.
+	pub id: String,
+	/// Name of the domain or entity.
+	pub name: Option<String>,
+	/// Description of the domain or entity.
+	pub description: Option<String>,
+}
+
+/// Thread of tweets sharing a conversation ID, linked through replies.
+#[derive(Debug)]
+pub struct Conversation {
+	/// ID of the conversation, i.e. of the tweet starting the thread.
+	pub id: String,
+	/// The tweet starting the thread with its replies, `None` if it is unavailable.
+	pub root: Option<ConversationNode>,
+	/// Replies whose parent is unavailable, e.g. deleted or older than the search reaches, with
+	/// their own replies.
+	pub orphans: Vec<ConversationNode>,
+	/// Why the root is unavailable, e.g. the `Not Found Error` of a deleted tweet.
+	pub errors: Vec<PartialError>,
+}
+impl Conversation {
+	/// Links the tweets to their parents through `referenced_tweets`.
+	fn build(id: String, root: Option<TweetData>, tweets: Vec<TweetData>) -> Self {
+		let mut seen = HashSet::new();
+		let tweets = root
+			.into_iter()
+			.chain(tweets)
+			.filter(|t| seen.insert(t.id.clone()))
+			.collect::<Vec<_>>();
+		let mut children = HashMap::<String, Vec<TweetData>>::new();
+		let mut root = None;
+		let mut orphans = Vec::new();
+
+		for tweet in tweets {
+			match tweet.referenced_tweet(ReferenceType::RepliedTo) {
+				_ if tweet.id == id => root = Some(tweet),
+				Some(parent) if seen.contains(parent) =>
+					children.entry(parent.to_owned()).or_default().push(tweet),
+				_ => orphans.push(tweet),
+			}
+		}
+
+		let root = root.map(|root| ConversationNode::build(root, &mut children));
+		let orphans = orphans
+			.into_iter()
+			.map(|orphan| ConversationNode::build(orphan, &mut children))
+			.collect();
+
+		Self { id, root, orphans, errors: Vec::new() }
+	}
+
+	/// Returns the number of tweets in the thread.
+	pub fn len(&self) -> usize {
+		self.root.iter().chain(&self.orphans).map(ConversationNode::count).sum()
+	}
+
+	/// Returns whether no tweet of the thread is available.
+	pub fn is_empty(&self) -> bool {
+		self.root.is_none() && self.orphans.is_empty()
+	}
+}
+
+/// Tweet of a thread with its replies, oldest first.
+#[derive(Debug)]
+pub struct ConversationNode {
+	/// The tweet.
+	pub tweet: TweetData,
+	/// Replies to the tweet.
+	pub replies: Vec<ConversationNode>,
+}
+impl ConversationNode {
+	fn build(tweet: TweetData, children: &mut HashMap<String, Vec<TweetData>>) -> Self {
+		let mut replies = children.remove(&tweet.id).unwrap_or_default();
+
+		// IDs are time-ordered snowflakes, so shorter ones are older.
+		replies.sort_by(|a, b| (a.id.len(), &a.id).cmp(&(b.id.len(), &b.id)));
+
+		let replies = replies.into_iter().map(|reply| Self::build(reply, children)).collect();
+
+		Self { tweet, replies }
+	}
+
+	/// Returns the number of tweets in the subtree, including this one.
+	pub fn count(&self) -> usize {
+		1 + self.replies.iter().map(Self::count).sum::<usize>()
+	}
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+	// crates.io
+	use reqwest::{Method, StatusCode};
+	// self
+	use super::*;
+	use crate::MockTransport;
+
+	fn tweet(id: &str, parent: Option<&str>) -> TweetData {
+		let referenced_tweets = parent
+			.map(|parent| serde_json::json!([{ "type": "replied_to", "id": parent }]))
+			.unwrap_or_else(|| serde_json::json!([]));
+
+		serde_json::from_value(serde_json::json!({
+			"id": id,
+			"text": id,
+			"conversation_id": "1",
+			"referenced_tweets": referenced_tweets,
+		}))
+		.unwrap()
+	}
+
+	fn ids(nodes: &[ConversationNode]) -> Vec<&str> {
+		nodes.iter().map(|node| node.tweet.id.as_str()).collect()
+	}
+
+	#[test]
+	fn build_sorts_replies_arriving_out_of_order() {
+		let conversation = Conversation::build(
+			"1".into(),
+			Some(tweet("1", None)),
+			vec![
+				tweet("12", Some("10")),
+				tweet("10", Some("1")),
+				tweet("9", Some("1")),
+				tweet("11", Some("10")),
+			],
+		);
+		let root = conversation.root.as_ref().unwrap();
+
+		assert_eq!(ids(&root.replies), ["9", "10"]);
+		assert_eq!(ids(&root.replies[1].replies), ["11", "12"]);
+		assert!(conversation.orphans.is_empty());
+		assert_eq!(conversation.len(), 5);
+	}
+
+	#[test]
+	fn build_keeps_replies_to_unavailable_tweets_as_orphans() {
+		let conversation = Conversation::build(
+			"1".into(),
+			Some(tweet("1", None)),
+			vec![tweet("3", Some("2")), tweet("4", Some("3")), tweet("5", Some("1"))],
+		);
+
+		assert_eq!(ids(&conversation.root.as_ref().unwrap().replies), ["5"]);
+		assert_eq!(ids(&conversation.orphans), ["3"]);
+		assert_eq!(ids(&conversation.orphans[0].replies), ["4"]);
+	}
+
+	#[test]
+	fn build_handles_missing_root() {
+		let conversation = Conversation::build(
+			"1".into(),
+			None,
+			vec![tweet("2", Some("1")), tweet("3", Some("2")), tweet("2", Some("1"))],
+		);
+
+		assert!(conversation.root.is_none());
+		assert_eq!(ids(&conversation.orphans), ["2"]);
+		assert_eq!(ids(&conversation.orphans[0].replies), ["3"]);
+		assert_eq!(conversation.len(), 2);
+	}
+
+	#[tokio::test]
+	async fn conversation_surfaces_not_found_error() {
+		let transport = MockTransport::new().with_response(
+			Method::GET,
+			"/2/tweets/1",
+			StatusCode::OK,
+			r#"{"errors":[{"value":"1","detail":"Could not find tweet with id: [1].","title":"Not Found Error","resource_type":"tweet","parameter":"id","resource_id":"1","type":"https://api.twitter.com/2/problems/resource-not-found"}]}"#,
+		);
+		let api = Api::with_bearer_token("token".into()).with_transport(transport);
+		let Err(Error::Api { api_error, .. }) = api.conversation("1", &Fields::new()).await else {
+			panic!("expected an API error");
+		};
+
+		assert_eq!(api_error.kind(), XErrorKind::ResourceNotFound);
+		assert_eq!(api_error.detail, "Could not find tweet with id: [1].");
+	}
+
+	#[tokio::test]
+	async fn conversation_keeps_errors_of_deleted_root() {
+		let transport = MockTransport::new()
+			.with_response(
+				Method::GET,
+				"/2/tweets/2",
+				StatusCode::OK,
+				r#"{"data":{"id":"2","text":"b","conversation_id":"1","referenced_tweets":[{"type":"replied_to","id":"1"}]}}"#,
+			)
+			.with_response(
+				Method::GET,
+				"/2/tweets/1",
+				StatusCode::OK,
+				r#"{"errors":[{"value":"1","detail":"Could not find tweet with id: [1].","title":"Not Found Error","resource_type":"tweet","parameter":"id","resource_id":"1","type":"https://api.twitter.com/2/problems/resource-not-found"}]}"#,
+			)
+			.with_response(
+				Method::GET,
+				"/2/tweets/search/recent",
+				StatusCode::OK,
+				r#"{"meta":{"result_count":0}}"#,
+			);
+		let api = Api::with_bearer_token("token".into()).with_transport(transport);
+		let conversation = api.conversation("2", &Fields::new()).await.unwrap();
+
+		assert!(conversation.root.is_none());
+		assert_eq!(conversation.errors[0].resource_id.as_deref(), Some("1"));
+	}
+
+	#[test]
+	fn parse_tweet_id_accepts_status_links() {
+		for link in [
+			"20",
+			" https://x.com/jack/status/20 ",
+			"twitter.com/jack/status/20",
+			"https://www.twitter.com/jack/statuses/20",
+			"https://mobile.x.com/jack/status/20/photo/1?s=20",
+			"https://x.com/i/web/status/20",
+		] {
+			assert_eq!(parse_tweet_id(link).unwrap(), "20", "{link}");
+		}
+	}
+
+	#[test]
+	fn parse_tweet_id_rejects_other_links() {
+		for link in [
+			"",
+			"https://x.com/jack",
+			"https://x.com/jack/status/abc",
+			"https://example.com/jack/status/20",
+		] {
+			assert!(parse_tweet_id(link).is_err(), "{link}");
+		}
+	}
 }
@@ -0,0 +1,318 @@
+//! X/Twitter V2 Users API
+
+// crates.io
+use serde::Deserialize;
+use serde_json::{Map, Value};
+// self
+use crate::{
+	ApiResponse, Envelope, Fields, Includes, RequestOptions, Timestamp,
+	auth::{AuthProvider, Scopes},
+	links::{self, WEB_BASE},
+	prelude::*,
+	tweets::{TweetData, TweetEntities},
+};
+
+/// Scopes required to look up users.
+const USER_READ_SCOPES: &[&str] = &[Scopes::TWEET_READ, Scopes::USERS_READ];
+
+/// Top-level paths of the X web app that are not user handles.
+const RESERVED_PATHS: &[&str] = &[
+	"compose",
+	"explore",
+	"hashtag",
+	"home",
+	"i",
+	"intent",
+	"messages",
+	"notifications",
+	"search",
+	"settings",
+	"share",
+];
+
+/// Trait for looking up users on X/Twitter API.
+pub trait ApiUser {
+	/// Looks up the authenticated user with the requested fields and expansions, e.g. to
+	/// discover its ID and handle right after authorization.
+	fn me(
+		&self,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<UserObject>>>>;
+
+	/// Looks up the authenticated user with the requested fields and expansions, overriding the
+	/// client configuration for this call.
+	fn me_with_options(
+		&self,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<UserObject>>>>;
+
+	/// Looks up a user by ID with the requested fields and expansions, e.g.
+	/// [`Expansion::PinnedTweetId`](crate::Expansion::PinnedTweetId) for the pinned tweet in
+	/// `includes.tweets`.
+	fn get_user(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<UserObject>>>>;
+
+	/// Looks up a user by ID with the requested fields and expansions, overriding the client
+	/// configuration for this call.
+	fn get_user_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> impl MaybeSend + Future<Output = Result<ApiResponse<Envelope<UserObject>>>>;
+}
+/// Implementation of user lookup functionality for the main API client.
+impl<A> ApiUser for Api<A>
+where
+	A: AuthProvider,
+{
+	async fn me(&self, fields: &Fields) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.me_with_options(fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+	async fn me_with_options(
+		&self,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.authenticator.ensure_scopes(USER_READ_SCOPES)?;
+
+		let url = self.url("/2/users/me");
+
+		self.get_with_options(&url, fields, options).await
+	}
+
+	async fn get_user(
+		&self,
+		id: &str,
+		fields: &Fields,
+	) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.get_user_with_options(id, fields, &RequestOptions::default()).await
+	}
+
+	#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(id = %id)))]
+	async fn get_user_with_options(
+		&self,
+		id: &str,
+		fields: &Fields,
+		options: &RequestOptions,
+	) -> Result<ApiResponse<Envelope<UserObject>>> {
+		self.authenticator.ensure_scopes(USER_READ_SCOPES)?;
+
+		let url = self.url(&format!("/2/users/{}", urlencoding::encode(id)));
+
+		self.get_with_options(&url, fields, options).await
+	}
+}
+
+/// Former name of [`UserObject`], kept for compatibility.
+pub type UserData = UserObject;
+
+/// User object returned by every user-returning endpoint and in `includes.users`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct UserObject {
+	/// Unique identifier for the user.
+	pub id: String,
+	/// Display name of the user.
+	pub name: String,
+	/// Handle of the user, without the leading `@`.
+	pub username: String,
+	/// When the account was created.
+	pub created_at: Option<Timestamp>,
+	/// Bio of the profile.
+	pub description: Option<String>,
+	/// Hashtags, cashtags, mentions and URLs parsed out of the bio and the profile link.
+	pub entities: Option<UserEntities>,
+	/// Free-form location of the profile, not necessarily a real place.
+	pub location: Option<String>,
+	/// Link to the avatar of the profile.
+	pub profile_image_url: Option<String>,
+	/// Whether the tweets of the user are visible to approved followers only.
+	pub protected: Option<bool>,
+	/// Whether the user has any kind of verification.
+	pub verified: Option<bool>,
+	/// Kind of verification of the user.
+	pub verified_type: Option<VerifiedType>,
+	/// Public follow and activity counts of the user.
+	pub public_metrics: Option<PublicUserMetrics>,
+	/// ID of the tweet pinned to the profile.
+	pub pinned_tweet_id: Option<String>,
+	/// Link set on the profile, shortened to `t.co`, see [`UserEntities::url`].
+	pub url: Option<String>,
+	/// Countries the user is withheld from.
+	pub withheld: Option<UserWithheld>,
+	/// Fields not modeled yet, e.g. ones added by X recently.
+	#[serde(flatten)]
+	pub extra: Map<String, Value>,
+}
+impl UserObject {
+	/// Returns when the account was created, parsed.
+	#[cfg(feature = "chrono")]
+	pub fn created_at_utc(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+		self.created_at.as_deref().and_then(crate::time::parse_timestamp)
+	}
+
+	/// Returns the link to the user's profile on X, unlike [`UserObject::url`] which is the link
+	/// set on the profile.
+	pub fn permalink(&self) -> String {
+		format!("{WEB_BASE}/{}", self.username)
+	}
+
+	/// Returns the pinned tweet found in the includes, expanded through
+	/// [`Expansion::PinnedTweetId`](crate::Expansion::PinnedTweetId).
+	pub fn pinned_tweet<'a>(&self, includes: &'a Includes) -> Option<&'a TweetData> {
+		let id = self.pinned_tweet_id.as_ref()?;
+
+		includes.tweets.iter().find(|t| &t.id == id)
+	}
+}
+
+/// Entities parsed out of the profile of a user.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct UserEntities {
+	/// Expanded form of the profile link, in `urls`.
+	pub url: Option<TweetEntities>,
+	/// Hashtags, cashtags, mentions and URLs in the bio.
+	pub description: Option<TweetEntities>,
+}
+
+/// Kind of verification of a user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerifiedType {
+	/// Verified through an X Premium subscription.
+	Blue,
+	/// Verified organization.
+	Business,
+	/// Verified government or multilateral organization.
+	Government,
+	/// Not verified.
+	None,
+}
+
+/// Public follow and activity counts of a user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct PublicUserMetrics {
+	/// Number of followers.
+	#[serde(default)]
+	pub followers_count: u64,
+	/// Number of followed users.
+	#[serde(default)]
+	pub following_count: u64,
+	/// Number of tweets, including retweets.
+	#[serde(default)]
+	pub tweet_count: u64,
+	/// Number of lists the user is a member of.
+	#[serde(default)]
+	pub listed_count: u64,
+	/// Number of likes given.
+	#[serde(default)]
+	pub like_count: u64,
+	/// Number of media posted.
+	#[serde(default)]
+	pub media_count: u64,
+}
+
+/// Withholding of a user in some countries.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct UserWithheld {
+	/// ISO 3166-1 alpha-2 codes of the countries the user is withheld from.
+	#[serde(default)]
+	pub country_codes: Vec<String>,
+}
+
+/// User referenced by a pasted link or handle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UserRef {
+	/// Numeric user ID, e.g. from `https://x.com/i/user/12`.
+	Id(String),
+	/// Handle without the leading `@`, e.g. from `https://x.com/jack`.
+	Username(String),
+}
+
+/// Returns the user referenced by a pasted profile link, an `@handle` or a bare ID.
+///
+/// Accepts `x.com` and `twitter.com` links with or without a scheme, including
+/// `/i/user/<id>` and `/intent/user?user_id=<id>` links.
+pub fn parse_user(link: &str) -> Result<UserRef> {
+	let link = link.trim();
+
+	if links::is_id(link) {
+		return Ok(UserRef::Id(link.into()));
+	}
+	if let Some(username) = link.strip_prefix('@')
+		&& links::is_username(username)
+	{
+		return Ok(UserRef::Username(username.into()));
+	}
+
+	links::path_segments(link)
+		.and_then(|(url, segments)| match segments.as_slice() {
+			[i, user, id, ..] if i == "i" && user == "user" && links::is_id(id) =>
+				Some(UserRef::Id(id.clone())),
+			[intent, user, ..]
+				if intent == "intent" && matches!(user.as_str(), "user" | "follow") =>
+				url.query_pairs().find_map(|(k, v)| match &*k {
+					"user_id" if links::is_id(&v) => Some(UserRef::Id(v.into_owned())),
+					"screen_name" if links::is_username(&v) =>
+						Some(UserRef::Username(v.into_owned())),
+					_ => None,
+				}),
+			[username, ..]
+				if links::is_username(username)
+					&& !RESERVED_PATHS.contains(&username.to_ascii_lowercase().as_str()) =>
+				Some(UserRef::Username(username.clone())),
+			_ => None,
+		})
+		.ok_or_else(|| Error::any(format!("not a user link: {link}")))
+}
+
+#[cfg(test)]
+mod tests {
+	// self
+	use super::*;
+
+	fn id(id: &str) -> UserRef {
+		UserRef::Id(id.into())
+	}
+
+	fn username(username: &str) -> UserRef {
+		UserRef::Username(username.into())
+	}
+
+	#[test]
+	fn parse_user_accepts_handles_and_profile_links() {
+		assert_eq!(parse_user(" 12 ").unwrap(), id("12"));
+		assert_eq!(parse_user("@jack").unwrap(), username("jack"));
+		assert_eq!(parse_user("https://x.com/jack").unwrap(), username("jack"));
+		assert_eq!(parse_user("twitter.com/Jack_01/status/20").unwrap(), username("Jack_01"));
+		assert_eq!(parse_user("https://mobile.twitter.com/jack?s=20").unwrap(), username("jack"));
+		assert_eq!(parse_user("https://x.com/i/user/12").unwrap(), id("12"));
+	}
+
+	#[test]
+	fn parse_user_accepts_intent_links() {
+		assert_eq!(parse_user("https://x.com/intent/user?user_id=12").unwrap(), id("12"));
+		assert_eq!(
+			parse_user("https://twitter.com/intent/follow?screen_name=jack").unwrap(),
+			username("jack")
+		);
+		assert!(parse_user("https://x.com/intent/user?user_id=jack").is_err());
+		assert!(parse_user("https://x.com/intent/user").is_err());
+	}
+
+	#[test]
+	fn parse_user_rejects_reserved_paths_and_other_hosts() {
+		assert!(parse_user("https://x.com/home").is_err());
+		assert!(parse_user("https://x.com/Explore").is_err());
+		assert!(parse_user("https://x.com/i/user/jack").is_err());
+		assert!(parse_user("https://example.com/jack").is_err());
+		assert!(parse_user("@this_handle_is_too_long").is_err());
+		assert!(parse_user("").is_err());
+	}
+}